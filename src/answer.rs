@@ -0,0 +1,107 @@
+use std::fmt::{self, Display, Formatter};
+
+/// Answer to a part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Rows of pixels forming letters, read by looking at them.
+    Image(Vec<Vec<bool>>),
+}
+
+impl Answer {
+    /// Interprets text such as a stored answer, recognizing integers and
+    /// images drawn with `#` and `.` on multiple lines.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim_end();
+        if let Ok(integer) = text.parse() {
+            Self::Integer(integer)
+        } else if text.contains('\n') && text.chars().all(|c| matches!(c, '#' | '.' | '\n')) {
+            Self::Image(
+                text.lines()
+                    .map(|line| line.chars().map(|c| c == '#').collect())
+                    .collect(),
+            )
+        } else {
+            Self::Text(text.into())
+        }
+    }
+
+    /// Whether the answer has to be displayed on lines of its own.
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Self::Integer(_) => false,
+            Self::Text(text) => text.contains('\n'),
+            Self::Image(_) => true,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(integer) => write!(f, "{integer}"),
+            Self::Text(text) => f.write_str(text),
+            Self::Image(rows) => {
+                for (i, row) in rows.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str("\n")?;
+                    }
+                    for &lit in row {
+                        f.write_str(if lit { "#" } else { "." })?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(integer: $t) -> Self {
+                    Self::Integer(integer as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Answer::parse("13337919186981"),
+            Answer::Integer(13_337_919_186_981)
+        );
+        assert_eq!(Answer::parse("-3\n"), Answer::Integer(-3));
+        assert_eq!(Answer::parse("BZLVHBWQF"), Answer::Text("BZLVHBWQF".into()));
+        assert_eq!(
+            Answer::parse("#.\n.#\n"),
+            Answer::Image(vec![vec![true, false], vec![false, true]])
+        );
+        assert_eq!(Answer::parse("#."), Answer::Text("#.".into()));
+    }
+
+    #[test]
+    fn display() {
+        let image = Answer::Image(vec![vec![true, false], vec![false, true]]);
+        assert_eq!(image.to_string(), "#.\n.#");
+        assert!(image.is_multiline());
+        assert_eq!(Answer::from(42_u64).to_string(), "42");
+        assert!(!Answer::from(42_u64).is_multiline());
+    }
+}
@@ -0,0 +1,184 @@
+//! Known-good answers, stored as a small subset of TOML:
+//!
+//! ```toml
+//! [day1]
+//! part1 = 68775
+//! part2 = "202585"
+//! ```
+//!
+//! Values may be integers, basic strings or multi-line basic strings. Strings
+//! are interpreted with [`Answer::parse`], so images are written with `#` and
+//! `.` in multi-line strings.
+
+use crate::answer::Answer;
+use std::collections::HashMap;
+use std::error::Error;
+
+pub struct Answers {
+    answers: HashMap<(usize, u8), Answer>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: Answer },
+    Missing,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let mut answers = HashMap::new();
+        let mut day = None;
+        let mut lines = (1..).zip(input.lines());
+        while let Some((line_number, line)) = lines.next() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[') {
+                let table = table
+                    .strip_suffix(']')
+                    .ok_or_else(|| format!("Line {line_number}: Unterminated table header"))?;
+                let number = table
+                    .trim()
+                    .strip_prefix("day")
+                    .and_then(|number| number.parse().ok())
+                    .ok_or_else(|| format!("Line {line_number}: Expected a [dayN] table"))?;
+                day = Some(number);
+                continue;
+            }
+            let day = day.ok_or_else(|| format!("Line {line_number}: Key outside of a table"))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Line {line_number}: Expected key = value"))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                key => return Err(format!("Line {line_number}: Unrecognized key {key}").into()),
+            };
+            let value = value.trim();
+            let value = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                let mut value = rest.to_string();
+                while !value.ends_with("\"\"\"") {
+                    let (_, line) = lines.next().ok_or_else(|| {
+                        format!("Line {line_number}: Unterminated multi-line string")
+                    })?;
+                    value.push('\n');
+                    value.push_str(line);
+                }
+                value.truncate(value.len() - 3);
+                // A newline immediately following the opening delimiter is trimmed
+                let value = value.strip_prefix('\n').unwrap_or(&value);
+                Answer::parse(&unescape(value).map_err(|e| format!("Line {line_number}: {e}"))?)
+            } else if let Some(rest) = value.strip_prefix('"') {
+                let value = rest
+                    .strip_suffix('"')
+                    .ok_or_else(|| format!("Line {line_number}: Unterminated string"))?;
+                Answer::parse(&unescape(value).map_err(|e| format!("Line {line_number}: {e}"))?)
+            } else {
+                let integer = value
+                    .replace('_', "")
+                    .parse()
+                    .map_err(|e| format!("Line {line_number}: {e}"))?;
+                Answer::Integer(integer)
+            };
+            if answers.insert((day, part), value).is_some() {
+                return Err(format!("Line {line_number}: Duplicate answer").into());
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: usize, part: u8) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn verify(&self, day: usize, part: u8, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+fn unescape(input: &str) -> Result<String, &'static str> {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            output.push(match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some('"') => '"',
+                Some('\\') => '\\',
+                _ => return Err("Unrecognized escape sequence"),
+            });
+        } else {
+            output.push(c);
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Answers, Verdict};
+    use crate::answer::Answer;
+    use crate::lines;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse(lines!(
+            "# Comment"
+            "[day1]"
+            "part1 = 1_234"
+            "part2 = \"A\\\"B\""
+            ""
+            "[day10]"
+            "part2 = \"\"\""
+            "#.#"
+            ".#.\"\"\""
+        ))
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some(&Answer::Integer(1234)));
+        assert_eq!(answers.get(1, 2), Some(&Answer::Text("A\"B".into())));
+        assert_eq!(answers.get(10, 1), None);
+        assert_eq!(
+            answers.get(10, 2),
+            Some(&Answer::Image(vec![
+                vec![true, false, true],
+                vec![false, true, false]
+            ]))
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let answers = Answers::parse(lines!("[day2]" "part1 = 15")).unwrap();
+        assert_eq!(answers.verify(2, 1, &15.into()), Verdict::Pass);
+        assert_eq!(
+            answers.verify(2, 1, &16.into()),
+            Verdict::Fail {
+                expected: 15.into()
+            }
+        );
+        assert_eq!(answers.verify(2, 2, &12.into()), Verdict::Missing);
+    }
+
+    #[test]
+    fn key_outside_table() {
+        assert!(Answers::parse("part1 = 1").is_err());
+    }
+
+    #[test]
+    fn stored_answers_parse() {
+        let answers = Answers::parse(include_str!("../answers.toml")).unwrap();
+        assert_eq!(
+            answers.get(15, 2),
+            Some(&Answer::Integer(13_337_919_186_981))
+        );
+    }
+}
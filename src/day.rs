@@ -0,0 +1,311 @@
+use crate::answer::Answer;
+use crate::input;
+use crate::params::{Param, Params};
+use crate::parse::{InputError, ParseError};
+use crate::report::{DayRun, PartReport};
+use crate::rng::Rng;
+use crate::timing::Timing;
+use std::error::Error;
+use std::io::BufRead;
+
+/// Solution to a single day's puzzle.
+///
+/// The input is parsed once with [`Day::parse`], and both parts are then
+/// solved from the parsed representation.
+pub trait Day: Sync {
+    /// Parsed representation of the input, which may borrow from it.
+    type Parsed<'a>;
+
+    /// Day of December on which the puzzle was published.
+    fn number(&self) -> usize;
+
+    fn title(&self) -> &'static str;
+
+    /// Puzzle constants which can be overridden from the command line.
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    /// Parses the input, pointing errors at the part of it they are about.
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>>;
+
+    fn part1<'a>(
+        &self,
+        parsed: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>>;
+
+    fn part2<'a>(
+        &self,
+        parsed: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>>;
+
+    /// Streaming entry point, for days able to solve both parts without
+    /// holding the whole input in memory.
+    fn streaming(&self) -> Option<&dyn Stream> {
+        None
+    }
+
+    /// Generates a random input which both parts can solve, with roughly
+    /// `size` entries such as lines, groups or monkeys.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+}
+
+/// Solution consuming its input line by line, with memory bounded no matter
+/// how large the input is.
+///
+/// The input isn't normalized beforehand, so implementations should read it
+/// with [`crate::parse::for_each_line`] or ignore line endings themselves.
+pub trait Stream: Sync {
+    fn stream_part1(
+        &self,
+        input: &mut dyn BufRead,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error>>;
+
+    fn stream_part2(
+        &self,
+        input: &mut dyn BufRead,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error>>;
+}
+
+/// Opens a fresh reader over the input, called once for every part and run.
+pub type Open<'a> = dyn Fn() -> Result<Box<dyn BufRead + 'a>, String> + 'a;
+
+/// Object safe interface to a [`Day`], used by the registry.
+pub trait Solution: Sync {
+    fn number(&self) -> usize;
+
+    fn title(&self) -> &'static str;
+
+    fn params(&self) -> &'static [Param];
+
+    /// Normalizes the input with [`input::normalize`], parses it once and
+    /// runs the given parts on it, repeating every step `runs` times to
+    /// measure it.
+    fn run(&self, input: &str, parts: &[u8], runs: usize, params: &Params) -> DayRun;
+
+    /// Runs the given parts with [`Day::streaming`], opening the input anew
+    /// for every part and run, or returns `None` when the day can't stream.
+    fn stream(&self, open: &Open<'_>, parts: &[u8], runs: usize, params: &Params)
+        -> Option<DayRun>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+}
+
+impl<T: Day> Solution for T {
+    fn number(&self) -> usize {
+        Day::number(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Day::title(self)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Day::params(self)
+    }
+
+    fn run(&self, input: &str, parts: &[u8], runs: usize, params: &Params) -> DayRun {
+        let input = &input::normalize(input);
+        let (parsed, parse_timing) =
+            match Timing::measure(runs, || self.parse(input).map_err(|e| e.locate(input))) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    return DayRun {
+                        parse: Err(e),
+                        parts: Vec::new(),
+                    }
+                }
+            };
+        let parts = parts
+            .iter()
+            .map(|&part| PartReport {
+                part,
+                outcome: Timing::measure(runs, || {
+                    match part {
+                        1 => self.part1(&parsed, params),
+                        2 => self.part2(&parsed, params),
+                        _ => Err(format!("Part {part} doesn't exist").into()),
+                    }
+                    .map_err(|e| e.to_string())
+                }),
+                verdict: None,
+            })
+            .collect();
+        DayRun {
+            parse: Ok(Some(parse_timing)),
+            parts,
+        }
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        Day::generate(self, rng, size)
+    }
+
+    fn stream(
+        &self,
+        open: &Open<'_>,
+        parts: &[u8],
+        runs: usize,
+        params: &Params,
+    ) -> Option<DayRun> {
+        let stream = self.streaming()?;
+        let mut reports = Vec::with_capacity(parts.len());
+        for &part in parts {
+            let outcome = Timing::measure(runs, || {
+                let mut input = open()?;
+                match part {
+                    1 => stream.stream_part1(&mut input, params),
+                    2 => stream.stream_part2(&mut input, params),
+                    _ => Err(format!("Part {part} doesn't exist").into()),
+                }
+            });
+            let outcome = match outcome {
+                Ok(outcome) => Ok(outcome),
+                Err(e) => match e.downcast::<InputError>() {
+                    Ok(e) => {
+                        return Some(DayRun {
+                            parse: Err(*e),
+                            parts: Vec::new(),
+                        })
+                    }
+                    Err(e) => Err(e.to_string()),
+                },
+            };
+            reports.push(PartReport {
+                part,
+                outcome,
+                verdict: None,
+            });
+        }
+        Some(DayRun {
+            parse: Ok(None),
+            parts: reports,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::params::Params;
+    use crate::rng::Rng;
+    use crate::SOLUTIONS;
+    use std::fs;
+    use std::io::BufRead;
+
+    #[test]
+    fn registry_is_ordered() {
+        for (number, day) in (1..).zip(SOLUTIONS) {
+            assert_eq!(day.number(), number);
+        }
+    }
+
+    #[test]
+    fn input_layouts() {
+        for day in SOLUTIONS {
+            let path = format!("src/day{}/input", day.number());
+            let input = fs::read_to_string(&path).unwrap();
+            let answers = |input: &str| {
+                let run = day.run(input, &[1, 2], 1, &Params::default());
+                assert!(run.parse.is_ok(), "{path}: {:?}", run.parse.err());
+                run.parts
+                    .into_iter()
+                    .map(|part| part.outcome.map(|(answer, _)| answer))
+                    .collect::<Vec<_>>()
+            };
+            let expected = answers(&input);
+            let crlf = input.replace('\n', "\r\n");
+            let trailing_whitespace = input.replace('\n', " \t\n");
+            for variant in [&crlf, input.trim_end(), &trailing_whitespace] {
+                assert_eq!(answers(variant), expected, "{path}");
+            }
+        }
+    }
+
+    #[test]
+    fn missing_part() {
+        let run = SOLUTIONS[0].run("1", &[3], 1, &Params::default());
+        assert!(run.parse.is_ok());
+        assert_eq!(
+            run.parts[0].outcome.as_ref().err().unwrap(),
+            "Part 3 doesn't exist"
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let run = SOLUTIONS[3].run("1-2", &[1, 2], 1, &Params::default());
+        assert_eq!(
+            run.parse.err().unwrap().to_string(),
+            "Missing first comma at line 1, column 3"
+        );
+        assert!(run.parts.is_empty());
+    }
+
+    #[test]
+    fn streaming_matches_run() {
+        let mut streamed = 0;
+        for day in SOLUTIONS {
+            let path = format!("src/day{}/input", day.number());
+            let file = fs::read_to_string(&path).unwrap();
+            for input in [&*file, "", "\n \n\n", "1\n\n\n2\n", "abcabc", "abcd"] {
+                let open =
+                    || -> Result<Box<dyn BufRead>, String> { Ok(Box::new(input.as_bytes())) };
+                let Some(run) = day.stream(&open, &[1, 2], 1, &Params::default()) else {
+                    continue;
+                };
+                streamed += 1;
+                let expected = day.run(input, &[1, 2], 1, &Params::default());
+                match (&run.parse, &expected.parse) {
+                    (Ok(None), Ok(_)) => {}
+                    (Err(e), Err(expected)) => {
+                        assert_eq!((e.line, e.column), (expected.line, expected.column));
+                        continue;
+                    }
+                    (parse, _) => panic!(
+                        "day {} on {input:?}: {:?}",
+                        day.number(),
+                        parse.as_ref().err()
+                    ),
+                }
+                for (part, expected) in run.parts.iter().zip(&expected.parts) {
+                    assert_eq!(
+                        part.outcome.as_ref().ok().map(|(answer, _)| answer),
+                        expected.outcome.as_ref().ok().map(|(answer, _)| answer),
+                        "day {} on {input:?}",
+                        day.number()
+                    );
+                }
+            }
+        }
+        assert_eq!(streamed, 18);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        for day in SOLUTIONS {
+            for seed in 0..4 {
+                let input = day.generate(&mut Rng::new(seed), 20);
+                assert_eq!(input, day.generate(&mut Rng::new(seed), 20));
+                let run = day.run(&input, &[1, 2], 1, &Params::default());
+                assert!(
+                    run.parse.is_ok(),
+                    "day {} seed {seed}: {:?}\n{input}",
+                    day.number(),
+                    run.parse.err()
+                );
+                for part in run.parts {
+                    assert!(
+                        part.outcome.is_ok(),
+                        "day {} seed {seed}: {:?}\n{input}",
+                        day.number(),
+                        part.outcome.err()
+                    );
+                }
+            }
+        }
+    }
+}
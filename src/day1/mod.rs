@@ -1,27 +1,119 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::{Day, Stream};
+use crate::params::Params;
+use crate::parse::{self, At, ParseError};
+use crate::rng::Rng;
 use std::collections::BinaryHeap;
-use std::num::ParseIntError;
+use std::error::Error;
+use std::io::BufRead;
 
-fn get_elfs(input: &str) -> impl Iterator<Item = Result<u32, ParseIntError>> + '_ {
+fn get_elfs(input: &str) -> impl Iterator<Item = Result<u32, ParseError<'_>>> + '_ {
     input.split("\n\n").map(|input| {
         input
             .lines()
-            .map(str::parse::<u32>)
+            .map(|line| line.parse::<u32>().at(line))
             .try_fold(0, |acc, line| Ok(acc + line?))
     })
 }
 
-pub(super) const DAY1: Solution = Solution {
-    part1: |input| {
-        Ok(get_elfs(input)
-            .try_fold(0, |acc, elf| elf.map(|elf| acc.max(elf)))?
-            .to_string())
-    },
-    part2: |input| {
-        let mut heap = get_elfs(input).collect::<Result<BinaryHeap<u32>, _>>()?;
-        Ok((0..3).map_while(|_| heap.pop()).sum::<u32>().to_string())
-    },
-};
+/// Largest totals of calories carried by an elf, in decreasing order, read
+/// without keeping every elf in memory.
+///
+/// Like [`Day1::parse`], an input without any line counts as a single elf
+/// carrying nothing.
+fn stream_top<const N: usize>(input: &mut dyn BufRead) -> Result<[u32; N], Box<dyn Error>> {
+    let mut top = [0; N];
+    let mut current = None;
+    let mut finish = |calories: u32| {
+        if let Some(i) = top.iter().position(|&top| calories > top) {
+            top[i..].rotate_right(1);
+            top[i] = calories;
+        }
+    };
+    parse::for_each_line(input, |line| {
+        if line.is_empty() {
+            finish(
+                current
+                    .take()
+                    .ok_or("Expected calories before a blank line")
+                    .at(line)?,
+            );
+        } else {
+            *current.get_or_insert(0) += line.parse::<u32>().at(line)?;
+        }
+        Ok(())
+    })?;
+    if let Some(calories) = current {
+        finish(calories);
+    }
+    Ok(top)
+}
+
+pub struct Day1;
+
+impl Day for Day1 {
+    type Parsed<'a> = Vec<u32>;
+
+    fn number(&self) -> usize {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        get_elfs(input).collect()
+    }
+
+    fn part1<'a>(
+        &self,
+        elves: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        Ok(elves
+            .iter()
+            .max()
+            .copied()
+            .ok_or("Expected at least one elf")?
+            .into())
+    }
+
+    fn part2<'a>(
+        &self,
+        elves: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut heap: BinaryHeap<u32> = elves.iter().copied().collect();
+        Ok((0..3).map_while(|_| heap.pop()).sum::<u32>().into())
+    }
+
+    fn streaming(&self) -> Option<&dyn Stream> {
+        Some(self)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let elves: Vec<String> = (0..size)
+            .map(|_| {
+                (0..rng.range(1..=6))
+                    .map(|_| format!("{}\n", rng.range(1_000..=60_000)))
+                    .collect()
+            })
+            .collect();
+        elves.join("\n")
+    }
+}
+
+impl Stream for Day1 {
+    fn stream_part1(&self, input: &mut dyn BufRead, _: &Params) -> Result<Answer, Box<dyn Error>> {
+        let [most] = stream_top::<1>(input)?;
+        Ok(most.into())
+    }
+
+    fn stream_part2(&self, input: &mut dyn BufRead, _: &Params) -> Result<Answer, Box<dyn Error>> {
+        Ok(stream_top::<3>(input)?.iter().sum::<u32>().into())
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -43,12 +135,12 @@ mod test {
         "10000"
     );
     test!(
-        DAY1.part1,
+        Day1.part1,
         example: EXAMPLE => 24000,
         input: 68775,
     );
     test!(
-        DAY1.part2,
+        Day1.part2,
         example: EXAMPLE => 45000,
         input: 202585,
     );
@@ -1,7 +1,52 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Params;
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
+use std::error::Error;
 
-pub(super) const DAY10: Solution = Solution {
-    part1: |input| {
+/// Instruction of the CPU program.
+#[derive(Clone, Copy)]
+pub enum Instruction {
+    Noop,
+    AddX(i32),
+}
+
+impl Instruction {
+    /// Parses a line such as `noop` or `addx -5`.
+    pub fn parse(line: &str) -> Result<Self, ParseError<'_>> {
+        if line == "noop" {
+            Ok(Self::Noop)
+        } else if let Some(add_x) = line.strip_prefix("addx ") {
+            Ok(Self::AddX(add_x.parse().at(add_x)?))
+        } else {
+            Err(ParseError::new(line, "Unrecognized instruction"))
+        }
+    }
+}
+
+pub struct Day10;
+
+impl Day for Day10 {
+    type Parsed<'a> = Vec<Instruction>;
+
+    fn number(&self) -> usize {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Cathode-Ray Tube"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        input.lines().map(Instruction::parse).collect()
+    }
+
+    fn part1<'a>(
+        &self,
+        program: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
         let mut x = 1;
         let mut output = 0;
         let mut cycles = 0;
@@ -12,46 +57,66 @@ pub(super) const DAY10: Solution = Solution {
                 next_output_cycle += 40;
             }
         };
-        for line in input.lines() {
-            if line == "noop" {
-                cycles += 1;
-            } else if let Some(add_x) = line.strip_prefix("addx ") {
-                cycles += 2;
-                add_output(cycles, x);
-                x += add_x.parse::<i32>()?;
-            } else {
-                return Err(format!("Unrecognized instruction {line}").into());
+        for &instruction in program {
+            match instruction {
+                Instruction::Noop => cycles += 1,
+                Instruction::AddX(add_x) => {
+                    cycles += 2;
+                    add_output(cycles, x);
+                    x += add_x;
+                }
             }
             add_output(cycles, x);
         }
-        Ok(output.to_string())
-    },
-    part2: |input| {
-        let mut output = String::new();
+        Ok(output.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        program: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut rows = vec![Vec::new()];
         let mut cycles = 0;
         let mut run_cycle = |x: i32| {
-            output.push(if x.abs_diff(cycles) <= 1 { '#' } else { '.' });
-            cycles += 1;
             if cycles == 40 {
-                output.push('\n');
+                rows.push(Vec::new());
                 cycles = 0;
             }
+            rows.last_mut().unwrap().push(x.abs_diff(cycles) <= 1);
+            cycles += 1;
         };
         let mut x = 1;
-        for line in input.lines() {
-            if line == "noop" {
-                run_cycle(x);
-            } else if let Some(add_x) = line.strip_prefix("addx ") {
-                run_cycle(x);
-                run_cycle(x);
-                x += add_x.parse::<i32>()?;
-            } else {
-                return Err(format!("Unrecognized instruction {line}").into());
+        for &instruction in program {
+            match instruction {
+                Instruction::Noop => run_cycle(x),
+                Instruction::AddX(add_x) => {
+                    run_cycle(x);
+                    run_cycle(x);
+                    x += add_x;
+                }
             }
         }
-        Ok(output)
-    },
-};
+        Ok(Answer::Image(rows))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        // Keeps the sprite on the screen most of the time.
+        let mut x = 1;
+        (0..size)
+            .map(|_| {
+                if rng.bool() {
+                    "noop\n".into()
+                } else {
+                    let target = rng.range(0..=39);
+                    let add_x = target - x;
+                    x = target;
+                    format!("addx {add_x}\n")
+                }
+            })
+            .collect()
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -205,13 +270,16 @@ mod test {
         "noop"
     );
     test!(
-        DAY10.part1,
+        Day10.part1,
         example: EXAMPLE => 13140,
         input: 17180,
     );
     mod part2 {
-        use super::super::DAY10;
+        use super::super::Day10;
         use super::EXAMPLE;
+        use crate::answer::Answer;
+        use crate::day::Day;
+        use crate::params::Params;
         use std::error::Error;
 
         #[test]
@@ -224,7 +292,10 @@ mod test {
                 "######......######......######......####\n",
                 "#######.......#######.......#######.....\n"
             );
-            assert_eq!((DAY10.part2)(EXAMPLE)?, output);
+            assert_eq!(
+                Day10.part2(&Day10.parse(EXAMPLE)?, &Params::default())?,
+                Answer::parse(output)
+            );
             Ok(())
         }
 
@@ -238,7 +309,10 @@ mod test {
                 "#.#..#....#..#.#....#.#..#....#..#.#..#.\n",
                 "#..#.####.#..#.#....#..#.####..##..###..\n",
             );
-            assert_eq!((DAY10.part2)(include_str!("input"))?, output);
+            assert_eq!(
+                Day10.part2(&Day10.parse(include_str!("input"))?, &Params::default())?,
+                Answer::parse(output)
+            );
             Ok(())
         }
     }
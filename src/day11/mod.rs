@@ -1,12 +1,19 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::{Param, Params};
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
 use std::{collections::VecDeque, error::Error};
 
-struct Monkeys {
+/// Monkeys passing items around, each keeping track of how many it inspected.
+#[derive(Clone)]
+pub struct Monkeys {
     monkeys: Vec<Monkey>,
 }
 
 impl Monkeys {
-    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+    /// Parses the notes describing each monkey, separated by blank lines.
+    pub fn parse(input: &str) -> Result<Self, ParseError<'_>> {
         let monkeys = input
             .split("\n\n")
             .map(Monkey::parse)
@@ -14,14 +21,16 @@ impl Monkeys {
         Ok(Self { monkeys })
     }
 
-    fn run_round(&mut self, modifier: impl Fn(u64) -> u64) {
+    /// Lets every monkey throw all its items once, applying `modifier` to the
+    /// worry level after each inspection.
+    pub fn run_round(&mut self, modifier: impl Fn(u64) -> u64) {
         for i in 0..self.monkeys.len() {
             while let Some(item) = self.monkeys[i].items.pop_front() {
                 let monkey = &mut self.monkeys[i];
                 monkey.inspected_items += 1;
                 let item = monkey.operation.execute(item);
                 let item = modifier(item);
-                let sent_to_monkey = if item % monkey.test == 0 {
+                let sent_to_monkey = if item.is_multiple_of(monkey.test) {
                     monkey.if_true
                 } else {
                     monkey.if_false
@@ -31,13 +40,15 @@ impl Monkeys {
         }
     }
 
-    fn monkey_business(&self) -> u64 {
+    /// Product of the number of items inspected by the two most active monkeys.
+    pub fn monkey_business(&self) -> u64 {
         let mut inspected_items: Vec<_> = self.monkeys.iter().map(|m| m.inspected_items).collect();
         inspected_items.sort();
         inspected_items.iter().rev().take(2).product::<u64>()
     }
 }
 
+#[derive(Clone)]
 struct Monkey {
     items: VecDeque<u64>,
     operation: Operation,
@@ -48,56 +59,67 @@ struct Monkey {
 }
 
 impl Monkey {
-    fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+    fn parse(input: &str) -> Result<Self, ParseError<'_>> {
+        let input = input.trim();
         let rest = input
-            .trim()
             .strip_prefix("Monkey ")
-            .ok_or("Missing monkey prefix")?;
+            .ok_or("Missing monkey prefix")
+            .at(input)?;
         let (_id, rest) = rest
             .split_once(":\n  Starting items: ")
-            .ok_or("Missing starting items")?;
+            .ok_or("Missing starting items")
+            .at(rest)?;
         let (items, rest) = rest
             .split_once("\n  Operation: new = old ")
-            .ok_or("Missing operation")?;
+            .ok_or("Missing operation")
+            .at(rest)?;
         let (operation, rest) = if let Some(rest) = rest.strip_prefix("* ") {
             let (value, rest) = rest
                 .split_once('\n')
-                .ok_or("Missing multiplication value")?;
+                .ok_or("Missing multiplication value")
+                .at(rest)?;
             let operation = if value == "old" {
                 Operation::Pow2
             } else {
-                Operation::Mul(value.parse()?)
+                Operation::Mul(value.parse().at(value)?)
             };
             (operation, rest)
         } else if let Some(rest) = rest.strip_prefix("+ ") {
-            let (value, rest) = rest.split_once('\n').ok_or("Missing addition value")?;
-            (Operation::Add(value.parse()?), rest)
+            let (value, rest) = rest
+                .split_once('\n')
+                .ok_or("Missing addition value")
+                .at(rest)?;
+            (Operation::Add(value.parse().at(value)?), rest)
         } else {
-            return Err("Expected a valid operation".into());
+            return Err(ParseError::new(rest, "Expected a valid operation"));
         };
         let rest = rest
             .strip_prefix("  Test: divisible by ")
-            .ok_or("Missing test")?;
+            .ok_or("Missing test")
+            .at(rest)?;
         let (test, rest) = rest
             .split_once("\n    If true: throw to monkey ")
-            .ok_or("Missing if true condition")?;
+            .ok_or("Missing if true condition")
+            .at(rest)?;
         let (if_true, if_false) = rest
             .split_once("\n    If false: throw to monkey ")
-            .ok_or("Missing if false condition")?;
+            .ok_or("Missing if false condition")
+            .at(rest)?;
         Ok(Self {
             items: items
                 .split(", ")
-                .map(str::parse)
+                .map(|item| item.parse().at(item))
                 .collect::<Result<_, _>>()?,
             operation,
-            test: test.parse()?,
-            if_true: if_true.parse()?,
-            if_false: if_false.parse()?,
+            test: test.parse().at(test)?,
+            if_true: if_true.parse().at(if_true)?,
+            if_false: if_false.parse().at(if_false)?,
             inspected_items: 0,
         })
     }
 }
 
+#[derive(Clone)]
 enum Operation {
     Mul(u64),
     Add(u64),
@@ -114,23 +136,102 @@ impl Operation {
     }
 }
 
-pub(super) const DAY11: Solution = Solution {
-    part1: |input| {
-        let mut monkeys = Monkeys::parse(input)?;
-        for _ in 0..20 {
+const PART1_ROUNDS: Param = Param {
+    name: "part1_rounds",
+    default: 20,
+    description: "Rounds simulated in part 1",
+};
+const PART2_ROUNDS: Param = Param {
+    name: "part2_rounds",
+    default: 10_000,
+    description: "Rounds simulated in part 2",
+};
+
+pub struct Day11;
+
+impl Day for Day11 {
+    type Parsed<'a> = Monkeys;
+
+    fn number(&self) -> usize {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Monkey in the Middle"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[PART1_ROUNDS, PART2_ROUNDS]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        Monkeys::parse(input)
+    }
+
+    fn part1<'a>(
+        &self,
+        monkeys: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut monkeys = monkeys.clone();
+        for _ in 0..params.get::<u32>(&PART1_ROUNDS)? {
             monkeys.run_round(|value| value / 3);
         }
-        Ok(monkeys.monkey_business().to_string())
-    },
-    part2: |input| {
-        let mut monkeys = Monkeys::parse(input)?;
+        Ok(monkeys.monkey_business().into())
+    }
+
+    fn part2<'a>(
+        &self,
+        monkeys: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut monkeys = monkeys.clone();
         let modulo: u64 = monkeys.monkeys.iter().map(|monkey| monkey.test).product();
-        for _ in 0..10_000 {
+        for _ in 0..params.get::<u32>(&PART2_ROUNDS)? {
             monkeys.run_round(|value| value % modulo);
         }
-        Ok(monkeys.monkey_business().to_string())
-    },
-};
+        Ok(monkeys.monkey_business().into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let count = rng.below(8) + 2;
+        // Distinct primes keep the product of the tests used by part 2 small
+        // enough for worry levels to be multiplied without overflowing.
+        let mut tests = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+        rng.shuffle(&mut tests);
+        let mut items = vec![1; count];
+        for _ in count..size {
+            items[rng.below(count)] += 1;
+        }
+        let monkeys: Vec<String> = (0..count)
+            .map(|i| {
+                let items: Vec<String> = (0..items[i])
+                    .map(|_| rng.range(50..=99).to_string())
+                    .collect();
+                // Part 1 only divides worry levels by 3, so they would grow
+                // without bound when squared or multiplied by more.
+                let operation = match rng.below(3) {
+                    0 => format!("* {}", rng.range(2..=3)),
+                    _ => format!("+ {}", rng.range(1..=8)),
+                };
+                let mut targets: Vec<usize> = (0..count).filter(|&j| j != i).collect();
+                rng.shuffle(&mut targets);
+                let (if_true, if_false) = (targets[0], targets[targets.len() - 1]);
+                format!(
+                    "Monkey {i}:\n  \
+                     Starting items: {}\n  \
+                     Operation: new = old {operation}\n  \
+                     Test: divisible by {}\n    \
+                     If true: throw to monkey {if_true}\n    \
+                     If false: throw to monkey {if_false}\n",
+                    items.join(", "),
+                    tests[i],
+                )
+            })
+            .collect();
+        monkeys.join("\n")
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -165,12 +266,12 @@ mod test {
         "    If false: throw to monkey 1"
     );
     test!(
-        DAY11.part1,
+        Day11.part1,
         example: EXAMPLE => 10605,
         input: 58786,
     );
     test!(
-        DAY11.part2,
+        Day11.part2,
         example: EXAMPLE => 2_713_310_158,
         input: 14_952_185_856,
     );
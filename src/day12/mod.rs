@@ -1,59 +1,107 @@
-use crate::{grid::Grid, Solution};
-use std::{collections::HashSet, error::Error, mem};
+use crate::{
+    answer::Answer, day::Day, grid::Grid, params::Params, parse::ParseError, rng::Rng, search,
+};
+use std::error::Error;
+
+/// Elevation of a square, the start and the best signal being at `a` and
+/// `z`.
+fn elevation(square: u8) -> u8 {
+    match square {
+        b'S' => b'a',
+        b'E' => b'z',
+        _ => square,
+    }
+}
 
+/// Fewest steps from any of `starts` to the best signal.
 fn run_search(
-    mut open_set: HashSet<(usize, usize)>,
-    grid: &Grid<'_>,
-) -> Result<String, Box<dyn Error>> {
-    let mut checked = HashSet::new();
-    for cost in 0.. {
-        for position @ (x, y) in mem::take(&mut open_set) {
-            let mut height = grid.at(x, y).unwrap();
-            if height == b'E' {
-                return Ok(cost.to_string());
-            }
-            if height == b'S' {
-                height = b'a';
-            }
-            if checked.insert(position) {
-                for (offset_x, offset_y) in [(usize::MAX, 0), (1, 0), (0, usize::MAX), (0, 1)] {
-                    let new_position @ (x, y) =
-                        (x.wrapping_add(offset_x), y.wrapping_add(offset_y));
-                    if let Some(mut c) = grid.at(x, y) {
-                        if c == b'E' {
-                            c = b'z';
-                        }
-                        if height + 1 >= c {
-                            open_set.insert(new_position);
-                        }
+    starts: impl IntoIterator<Item = (usize, usize)>,
+    grid: &Grid<u8>,
+) -> Result<u64, Box<dyn Error>> {
+    let neighbors = |&(x, y): &(usize, usize)| {
+        let height = elevation(*grid.get(x, y).unwrap());
+        grid.neighbors4((x, y))
+            .filter(move |&(_, &c)| elevation(c) <= height + 1)
+            .map(|(position, _)| position)
+    };
+    let is_goal = |&(x, y): &(usize, usize)| grid.get(x, y) == Some(&b'E');
+    let path = search::bfs(starts, neighbors, is_goal).ok_or("Unable to reach best signal")?;
+    Ok(path.cost)
+}
+
+pub struct Day12;
+
+impl Day for Day12 {
+    type Parsed<'a> = Grid<u8>;
+
+    fn number(&self) -> usize {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Hill Climbing Algorithm"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        Grid::parse(input)
+    }
+
+    fn part1<'a>(
+        &self,
+        grid: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let start = grid.find(&b'S').ok_or("Unable to find start point")?;
+        Ok(run_search([start], grid)?.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        grid: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let starts = (0..grid.width())
+            .flat_map(|x| (0..grid.height()).map(move |y| (x, y)))
+            .filter(|&(x, y)| elevation(*grid.get(x, y).unwrap()) == b'a');
+        Ok(run_search(starts, grid)?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let width = size.max(26);
+        let height = (size / 2).max(1);
+        let path_row = rng.below(height);
+        let mut input = String::new();
+        for y in 0..height {
+            let mut elevation = rng.range(0..=25);
+            let row: String = (0..width)
+                .map(|x| {
+                    if y != path_row {
+                        elevation = (elevation + rng.range(-2..=1)).clamp(0, 25);
+                        return char::from(b'a' + elevation as u8);
                     }
-                }
-            }
-        }
-        if open_set.is_empty() {
-            break;
+                    // The path row climbs at most one step at a time, from S
+                    // in the first column to E in the last one.
+                    if x == 0 {
+                        elevation = 0;
+                        return 'S';
+                    }
+                    if 25 - elevation > (width - 1 - x) as i64 || elevation < 25 && rng.bool() {
+                        elevation += 1;
+                    }
+                    if x == width - 1 {
+                        'E'
+                    } else {
+                        char::from(b'a' + elevation as u8)
+                    }
+                })
+                .collect();
+            input.push_str(&row);
+            input.push('\n');
         }
+        input
     }
-    Err("Unable to reach best signal".into())
 }
 
-pub(super) const DAY12: Solution = Solution {
-    part1: |input| {
-        let grid = Grid::parse(input)?;
-        let start = grid.find(b'S').ok_or("Unable to find start point")?;
-        let open_set = HashSet::from_iter([start]);
-        run_search(open_set, &grid)
-    },
-    part2: |input| {
-        let grid = Grid::parse(input)?;
-        let open_set = (0..grid.width())
-            .flat_map(|x| (0..grid.height()).map(move |y| (x, y)))
-            .filter(|&(x, y)| b"aS".contains(&grid.at(x, y).unwrap()))
-            .collect();
-        run_search(open_set, &grid)
-    },
-};
-
 #[cfg(test)]
 mod test {
     use crate::{lines, test};
@@ -65,12 +113,12 @@ mod test {
         "abdefghi"
     );
     test!(
-        DAY12.part1,
+        Day12.part1,
         example: EXAMPLE => 31,
         input: 361,
     );
     test!(
-        DAY12.part2,
+        Day12.part2,
         example: EXAMPLE => 29,
         input: 354,
     );
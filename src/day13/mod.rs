@@ -1,15 +1,20 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Params;
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
 use core::slice;
 use std::{cmp::Ordering, error::Error};
 
+/// Packet of the distress signal, ordered as described by the puzzle.
 #[derive(Debug)]
-enum Signal {
+pub enum Signal {
     Array(Vec<Signal>),
     Int(u8),
 }
 
 impl Signal {
-    fn parse_partial(input: &str) -> Result<(Signal, &str), Box<dyn Error>> {
+    fn parse_partial(input: &str) -> Result<(Signal, &str), ParseError<'_>> {
         if let Some(mut rest) = input.strip_prefix('[') {
             if let Some(rest) = rest.strip_prefix(']') {
                 return Ok((Self::Array(Vec::new()), rest));
@@ -23,24 +28,37 @@ impl Signal {
                 } else if let Some(after_comma) = after_partial.strip_prefix(',') {
                     rest = after_comma;
                 } else {
-                    return Err("Expected ] or , after array element".into());
+                    return Err(ParseError::new(
+                        after_partial,
+                        "Expected ] or , after array element",
+                    ));
                 }
             }
         } else if let Some(index) = input.find([',', ']']) {
             let (integer, rest) = input.split_at(index);
-            Ok((Self::Int(integer.parse()?), rest))
+            Ok((Self::Int(integer.parse().at(integer)?), rest))
         } else {
-            Err("Unexpected input".into())
+            Err(ParseError::new(input, "Unexpected input"))
         }
     }
 
-    fn parse(input: &str) -> Result<Signal, Box<dyn Error>> {
+    /// Parses a single packet such as `[1,[2,3]]`.
+    pub fn parse(input: &str) -> Result<Signal, ParseError<'_>> {
         match Self::parse_partial(input.trim())? {
             (signal, "") => Ok(signal),
-            _ => Err("Expected string to match completely".into()),
+            (_, rest) => Err(ParseError::new(rest, "Expected string to match completely")),
         }
     }
 
+    fn to_array(&self) -> &[Signal] {
+        match self {
+            Self::Array(array) => array,
+            int => slice::from_ref(int),
+        }
+    }
+}
+
+impl Ord for Signal {
     fn cmp(&self, other: &Self) -> Ordering {
         if let (Self::Int(a), Self::Int(b)) = (self, other) {
             return a.cmp(b);
@@ -55,39 +73,88 @@ impl Signal {
         }
         a.len().cmp(&b.len())
     }
+}
 
-    fn to_array(&self) -> &[Signal] {
-        match self {
-            Self::Array(array) => array,
-            int => slice::from_ref(int),
-        }
+impl PartialOrd for Signal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-pub(super) const DAY13: Solution = Solution {
-    part1: |input| {
+/// Equality as defined by the ordering, so `1` equals `[1]`.
+impl PartialEq for Signal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Signal {}
+
+/// Random packet nested at most three lists deep below `depth`.
+fn random_packet(rng: &mut Rng, depth: usize) -> String {
+    let elements: Vec<String> = (0..rng.below(5))
+        .map(|_| {
+            if depth < 3 && rng.bool() {
+                random_packet(rng, depth + 1)
+            } else {
+                rng.range(0..=10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", elements.join(","))
+}
+
+pub struct Day13;
+
+impl Day for Day13 {
+    type Parsed<'a> = Vec<(Signal, Signal)>;
+
+    fn number(&self) -> usize {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Distress Signal"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        input
+            .split("\n\n")
+            .map(|pair| {
+                let (a, b) = pair
+                    .split_once('\n')
+                    .ok_or("Expected two elements in a pair")
+                    .at(pair)?;
+                Ok((Signal::parse(a)?, Signal::parse(b)?))
+            })
+            .collect()
+    }
+
+    fn part1<'a>(
+        &self,
+        pairs: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
         let mut sum = 0;
-        for (i, pair) in (1..).zip(input.split("\n\n")) {
-            let (a, b) = pair
-                .split_once('\n')
-                .ok_or("Expected two elements in a pair")?;
-            let a = Signal::parse(a)?;
-            let b = Signal::parse(b)?;
-            if a.cmp(&b).is_le() {
+        for (i, (a, b)) in (1..).zip(pairs) {
+            if a <= b {
                 sum += i;
             }
         }
-        Ok(sum.to_string())
-    },
-    part2: |input| {
-        let mut vec = input
-            .split("\n\n")
-            .flat_map(|pair| pair.lines())
-            .map(Signal::parse)
-            .collect::<Result<Vec<_>, _>>()?;
-        vec.push(Signal::Array(vec![Signal::Array(vec![Signal::Int(2)])]));
-        vec.push(Signal::Array(vec![Signal::Array(vec![Signal::Int(6)])]));
-        vec.sort_unstable_by(Signal::cmp);
+        Ok(sum.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        pairs: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let first_divider = Signal::Array(vec![Signal::Array(vec![Signal::Int(2)])]);
+        let second_divider = Signal::Array(vec![Signal::Array(vec![Signal::Int(6)])]);
+        let mut vec: Vec<&Signal> = pairs.iter().flat_map(|(a, b)| [a, b]).collect();
+        vec.push(&first_divider);
+        vec.push(&second_divider);
+        vec.sort_unstable();
         let mut first_packet = None;
         let mut second_packet = None;
         for (i, elem) in (1..).zip(vec) {
@@ -103,9 +170,16 @@ pub(super) const DAY13: Solution = Solution {
         }
         let decoder_key = first_packet.ok_or("Missing first packet")?
             * second_packet.ok_or("Missing second packet")?;
-        Ok(decoder_key.to_string())
-    },
-};
+        Ok(decoder_key.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let pairs: Vec<String> = (0..size)
+            .map(|_| format!("{}\n{}\n", random_packet(rng, 0), random_packet(rng, 0)))
+            .collect();
+        pairs.join("\n")
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -136,12 +210,12 @@ mod test {
         "[1,[2,[3,[4,[5,6,0]]]],8,9]"
     );
     test!(
-        DAY13.part1,
+        Day13.part1,
         example: EXAMPLE => 13,
         input: 5_659,
     );
     test!(
-        DAY13.part2,
+        Day13.part2,
         example: EXAMPLE => 140,
         input: 22_110,
     );
@@ -1,83 +1,174 @@
-use std::{collections::HashSet, error::Error};
+use std::error::Error;
 
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::grid::Grid;
+use crate::params::Params;
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
 
-fn parse_input(input: &str) -> Result<HashSet<(i32, i32)>, Box<dyn Error>> {
-    let mut grid = HashSet::new();
-    for line in input.lines() {
-        let mut path = line
-            .split(" -> ")
-            .map(|point| -> Result<_, Box<dyn Error>> {
-                let (x, y) = point.split_once(',').ok_or("Unable to parse a point")?;
-                Ok((x.parse()?, y.parse()?))
-            });
-        let mut previous = path.next().expect("First point")?;
-        for point in path {
-            let point = point?;
-            let mut x_range = [previous.0, point.0];
+/// Slice of the cave wide enough for sand to pile up on the floor of part 2.
+#[derive(Clone)]
+pub struct Cave {
+    /// Whether each position is blocked by rock or sand.
+    blocked: Grid<bool>,
+    /// Column of the source of sand.
+    source: usize,
+    /// Lowest row containing rock.
+    max_y: usize,
+}
+
+impl Cave {
+    fn is_blocked(&self, x: usize, y: usize) -> bool {
+        self.blocked.get(x, y) != Some(&false)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Cave, ParseError<'_>> {
+    let paths = input
+        .lines()
+        .map(|line| {
+            line.split(" -> ")
+                .map(|point| {
+                    let (x, y) = point
+                        .split_once(',')
+                        .ok_or("Unable to parse a point")
+                        .at(point)?;
+                    Ok((x.parse::<i32>().at(x)?, y.parse::<usize>().at(y)?))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let points = || paths.iter().flatten();
+    let max_y = points()
+        .map(|point| point.1)
+        .max()
+        .ok_or("No points parsed")
+        .at(input)?;
+    let reach = max_y as i32 + 2;
+    let left = points()
+        .map(|point| point.0)
+        .min()
+        .unwrap()
+        .min(500 - reach);
+    let right = points()
+        .map(|point| point.0)
+        .max()
+        .unwrap()
+        .max(500 + reach);
+    let mut blocked = Grid::new((right - left + 1) as usize, max_y + 2, false);
+    for path in &paths {
+        for pair in path.windows(2) {
+            let mut x_range = [pair[0].0, pair[1].0];
             x_range.sort_unstable();
-            let mut y_range = [previous.1, point.1];
+            let mut y_range = [pair[0].1, pair[1].1];
             y_range.sort_unstable();
             for x in x_range[0]..=x_range[1] {
                 for y in y_range[0]..=y_range[1] {
-                    grid.insert((x, y));
+                    blocked.set((x - left) as usize, y, true);
                 }
             }
-            previous = point;
         }
     }
-    Ok(grid)
+    Ok(Cave {
+        blocked,
+        source: (500 - left) as usize,
+        max_y,
+    })
 }
 
-fn get_max_y(grid: &HashSet<(i32, i32)>) -> Result<i32, &'static str> {
-    grid.iter()
-        .map(|point| point.1)
-        .max()
-        .ok_or("No points parsed")
-}
-
-fn run_part2_simulation(
-    grid: &mut HashSet<(i32, i32)>,
-    position @ (x, y): (i32, i32),
-    max_y: i32,
-) -> u32 {
+fn run_part2_simulation(cave: &mut Cave, (x, y): (usize, usize)) -> u32 {
     let mut sum = 0;
     for offset in [0, -1, 1] {
-        let new_position = (x + offset, y + 1);
-        if !grid.contains(&new_position) && y != max_y + 1 {
-            sum += run_part2_simulation(grid, new_position, max_y);
+        let new_x = x.wrapping_add_signed(offset);
+        if !cave.is_blocked(new_x, y + 1) && y != cave.max_y + 1 {
+            sum += run_part2_simulation(cave, (new_x, y + 1));
         }
     }
-    grid.insert(position);
+    cave.blocked.set(x, y, true);
     sum + 1
 }
 
-pub(super) const DAY14: Solution = Solution {
-    part1: |input| {
-        let mut grid = parse_input(input)?;
-        let max_y = get_max_y(&grid)?;
+pub struct Day14;
+
+impl Day for Day14 {
+    type Parsed<'a> = Cave;
+
+    fn number(&self) -> usize {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Regolith Reservoir"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        parse_input(input)
+    }
+
+    fn part1<'a>(
+        &self,
+        cave: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut cave = cave.clone();
         'counting: for count in 0.. {
-            let mut sand_x = 500;
-            'falling: for y in 0..=max_y {
+            let mut sand_x = cave.source;
+            'falling: for y in 0..=cave.max_y {
                 for offset in [0, -1, 1] {
-                    if !grid.contains(&(sand_x + offset, y + 1)) {
-                        sand_x += offset;
+                    let x = sand_x.wrapping_add_signed(offset);
+                    if !cave.is_blocked(x, y + 1) {
+                        sand_x = x;
                         continue 'falling;
                     }
                 }
-                grid.insert((sand_x, y));
+                cave.blocked.set(sand_x, y, true);
                 continue 'counting;
             }
-            return Ok(count.to_string());
+            return Ok(count.into());
         }
         unreachable!()
-    },
-    part2: |input| {
-        let mut grid = parse_input(input)?;
-        let max_y = get_max_y(&grid)?;
-        Ok(run_part2_simulation(&mut grid, (500, 0), max_y).to_string())
-    },
-};
+    }
+
+    fn part2<'a>(
+        &self,
+        cave: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut cave = cave.clone();
+        let source = cave.source;
+        Ok(run_part2_simulation(&mut cave, (source, 0)).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        // Rocks stay strictly below the diagonals going down from the source,
+        // which sand can't entirely fill, so that sand always ends up flowing
+        // into the abyss in part 1.
+        let spread = (3 * size.isqrt()).max(10) as i32;
+        let x_range = 500 - spread..=500 + spread;
+        let y_range = spread + 1..=spread * 3;
+        (0..size)
+            .map(|_| {
+                let mut point = (
+                    rng.range(i64::from(*x_range.start())..=i64::from(*x_range.end())) as i32,
+                    rng.range(i64::from(*y_range.start())..=i64::from(*y_range.end())) as i32,
+                );
+                let mut points = vec![format!("{},{}", point.0, point.1)];
+                let horizontal = rng.bool();
+                for i in 0..rng.range(1..=4) {
+                    let offset = rng.range(-6..=6) as i32;
+                    if (i % 2 == 0) == horizontal {
+                        point.0 = (point.0 + offset).clamp(*x_range.start(), *x_range.end());
+                    } else {
+                        point.1 = (point.1 + offset).clamp(*y_range.start(), *y_range.end());
+                    }
+                    points.push(format!("{},{}", point.0, point.1));
+                }
+                points.join(" -> ") + "\n"
+            })
+            .collect()
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -87,12 +178,12 @@ mod test {
         "503,4 -> 502,4 -> 502,9 -> 494,9"
     );
     test!(
-        DAY14.part1,
+        Day14.part1,
         example: EXAMPLE => 24,
         input: 672,
     );
     test!(
-        DAY14.part2,
+        Day14.part2,
         example: EXAMPLE => 93,
         input: 26_831,
     );
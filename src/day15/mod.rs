@@ -1,8 +1,13 @@
-use std::{collections::HashSet, error::Error, ops::Range};
+use std::{collections::HashSet, error::Error, fmt::Write, ops::Range};
 
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::{Param, Params};
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
 
-struct Sensor {
+/// Sensor along with the closest beacon it detected.
+pub struct Sensor {
     sensor_x: i32,
     sensor_y: i32,
     beacon_x: i32,
@@ -10,33 +15,49 @@ struct Sensor {
 }
 
 impl Sensor {
-    fn distance(&self) -> u32 {
+    /// Position of the sensor.
+    pub fn position(&self) -> (i32, i32) {
+        (self.sensor_x, self.sensor_y)
+    }
+
+    /// Position of the closest beacon.
+    pub fn beacon(&self) -> (i32, i32) {
+        (self.beacon_x, self.beacon_y)
+    }
+
+    /// Manhattan distance to the closest beacon, within which there can't be
+    /// any other beacon.
+    pub fn distance(&self) -> u32 {
         self.sensor_x.abs_diff(self.beacon_x) + self.sensor_y.abs_diff(self.beacon_y)
     }
 }
 
-fn parse_sensor(line: &str) -> Result<Sensor, Box<dyn Error>> {
+fn parse_sensor(line: &str) -> Result<Sensor, ParseError<'_>> {
     let rest = line
         .strip_prefix("Sensor at x=")
-        .ok_or("Expected sensor x position")?;
+        .ok_or("Expected sensor x position")
+        .at(line)?;
     let (sensor_x, rest) = rest
         .split_once(", y=")
-        .ok_or("Expected sensor y position")?;
+        .ok_or("Expected sensor y position")
+        .at(rest)?;
     let (sensor_y, rest) = rest
         .split_once(": closest beacon is at x=")
-        .ok_or("Expected beacon x position")?;
+        .ok_or("Expected beacon x position")
+        .at(rest)?;
     let (beacon_x, beacon_y) = rest
         .split_once(", y=")
-        .ok_or("Expected beacon y position")?;
+        .ok_or("Expected beacon y position")
+        .at(rest)?;
     Ok(Sensor {
-        sensor_x: sensor_x.parse()?,
-        sensor_y: sensor_y.parse()?,
-        beacon_x: beacon_x.parse()?,
-        beacon_y: beacon_y.parse()?,
+        sensor_x: sensor_x.parse().at(sensor_x)?,
+        sensor_y: sensor_y.parse().at(sensor_y)?,
+        beacon_x: beacon_x.parse().at(beacon_x)?,
+        beacon_y: beacon_y.parse().at(beacon_y)?,
     })
 }
 
-fn parse_sensors(input: &str) -> Result<Vec<Sensor>, Box<dyn Error>> {
+fn parse_sensors(input: &str) -> Result<Vec<Sensor>, ParseError<'_>> {
     input.lines().map(parse_sensor).collect()
 }
 
@@ -49,7 +70,7 @@ fn get_ranges(sensors: &[Sensor], row: i32) -> Vec<Range<i32>> {
         let distance = sensor.distance();
         let y_diff = row.abs_diff(sensor_y);
         let width = match distance.checked_sub(y_diff) {
-            Some(0) | None => continue,
+            None => continue,
             Some(width) => width as i32,
         };
         let mut range = sensor_x - width..sensor_x + width + 1;
@@ -83,48 +104,132 @@ fn find_invalid_beacon_positions(sensors: &[Sensor], row: i32) -> Result<usize,
         - beacons_on_row.len())
 }
 
-fn part2<const SEARCH_SPACE: i32>(input: &str) -> Result<String, Box<dyn Error>> {
-    let sensors = parse_sensors(input)?;
+fn find_tuning_frequency(sensors: &[Sensor], search_space: i32) -> Result<i64, Box<dyn Error>> {
     for sensor @ &Sensor {
         sensor_x, sensor_y, ..
-    } in &sensors
+    } in sensors
     {
         let distance = (sensor.distance() + 1) as i32;
         for x_offset in 0..distance * 2 + 1 {
             let x_middle_distance = distance.abs_diff(x_offset) as i32;
             let y_offset = distance - x_middle_distance;
             'y: for y in [sensor_y - y_offset, sensor_y + y_offset] {
-                if y < 0 || y >= SEARCH_SPACE {
+                if y < 0 || y >= search_space {
                     continue;
                 }
                 let x = sensor_x + x_offset - distance;
-                if x < 0 || x >= SEARCH_SPACE {
+                if x < 0 || x >= search_space {
                     continue;
                 }
-                for sensor in &sensors {
+                for sensor in sensors {
                     if sensor.sensor_x.abs_diff(x) + sensor.sensor_y.abs_diff(y)
                         <= sensor.distance()
                     {
                         continue 'y;
                     }
                 }
-                return Ok((i64::from(x) * 4_000_000 + i64::from(y)).to_string());
+                return Ok(i64::from(x) * 4_000_000 + i64::from(y));
             }
         }
     }
     Err("Unable to find tuning frequency".into())
 }
 
-pub(super) const DAY15: Solution = Solution {
-    part1: |input| {
-        Ok(find_invalid_beacon_positions(&parse_sensors(input)?, 2_000_000)?.to_string())
-    },
-    part2: part2::<4000000>,
+const ROW: Param = Param {
+    name: "row",
+    default: 2_000_000,
+    description: "Row on which positions are counted in part 1",
+};
+const SEARCH_SPACE: Param = Param {
+    name: "search_space",
+    default: 4_000_000,
+    description: "Size of the area searched for the distress beacon in part 2",
 };
 
+pub struct Day15;
+
+impl Day for Day15 {
+    type Parsed<'a> = Vec<Sensor>;
+
+    fn number(&self) -> usize {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Beacon Exclusion Zone"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[ROW, SEARCH_SPACE]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        parse_sensors(input)
+    }
+
+    fn part1<'a>(
+        &self,
+        sensors: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        Ok(find_invalid_beacon_positions(sensors, params.get(&ROW)?)?.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        sensors: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        Ok(find_tuning_frequency(sensors, params.get(&SEARCH_SPACE)?)?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let search_space = SEARCH_SPACE.default;
+        let point = |rng: &mut Rng| {
+            (
+                rng.range(0..=search_space - 1),
+                rng.range(0..=search_space - 1),
+            )
+        };
+        // No sensor reaches the distress beacon, so that part 2 finds a spot,
+        // and sensors reach less far as there are more of them, so that part 2
+        // doesn't have to go around all of them before finding one.
+        let distress_beacon = point(rng);
+        let max_distance = search_space / (2 * size.isqrt() as i64).max(1);
+        let mut input = String::new();
+        for _ in 0..size {
+            let (sensor_x, sensor_y) = loop {
+                let sensor = point(rng);
+                if sensor != distress_beacon {
+                    break sensor;
+                }
+            };
+            let reach = sensor_x.abs_diff(distress_beacon.0) + sensor_y.abs_diff(distress_beacon.1);
+            let distance = rng.range(0..=(reach as i64 - 1).min(max_distance));
+            let x_distance = rng.range(0..=distance);
+            let y_distance = distance - x_distance;
+            let beacon_x = if rng.bool() {
+                sensor_x + x_distance
+            } else {
+                sensor_x - x_distance
+            };
+            let beacon_y = if rng.bool() {
+                sensor_y + y_distance
+            } else {
+                sensor_y - y_distance
+            };
+            writeln!(
+                input,
+                "Sensor at x={sensor_x}, y={sensor_y}: closest beacon is at x={beacon_x}, y={beacon_y}"
+            )
+            .unwrap();
+        }
+        input
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{find_invalid_beacon_positions, parse_sensors, part2};
     use crate::{lines, test};
     const EXAMPLE: &str = lines!(
         "Sensor at x=2, y=18: closest beacon is at x=-2, y=15"
@@ -143,17 +248,15 @@ mod test {
         "Sensor at x=20, y=1: closest beacon is at x=15, y=3"
     );
     test!(
-        DAY15.part1,
-        fn example() {
-            assert_eq!(find_invalid_beacon_positions(&parse_sensors(EXAMPLE).unwrap(), 10).unwrap(), 26);
-        }
+        Day15.part1,
+        example: EXAMPLE, row = 10 => 26,
+        beacon_at_tip: "Sensor at x=0, y=0: closest beacon is at x=0, y=1", row = 1 => 0,
+        tip: "Sensor at x=0, y=0: closest beacon is at x=1, y=0", row = -1 => 1,
         input: 5_256_611,
     );
     test!(
-        DAY15.part2,
-        fn example() {
-            assert_eq!(part2::<20>(EXAMPLE).unwrap(), "56000011");
-        }
+        Day15.part2,
+        example: EXAMPLE, search_space = 20 => 56_000_011,
         input: 13_337_919_186_981,
     );
 }
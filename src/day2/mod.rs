@@ -1,16 +1,24 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Params;
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
+use std::error::Error;
 
+/// Shape played in a round of rock paper scissors.
 #[derive(Clone, Copy, PartialEq, Eq)]
-enum Shape {
+pub enum Shape {
     Rock,
     Paper,
     Scissors,
 }
 
-fn parse_line(line: &str) -> Result<(Shape, u8), &'static str> {
+fn parse_line(line: &str) -> Result<(Shape, u8), ParseError<'_>> {
     match line.as_bytes() {
-        &[opponent, b' ', response] => Ok((Shape::parse_opponent(opponent)?, response)),
-        _ => Err("Line doesn't match expected pattern"),
+        &[opponent, b' ', response] => {
+            Ok((Shape::parse_opponent(opponent).at(&line[..1])?, response))
+        }
+        _ => Err(ParseError::new(line, "Line doesn't match expected pattern")),
     }
 }
 
@@ -24,7 +32,8 @@ impl Shape {
         })
     }
 
-    fn shape_score(self) -> u32 {
+    /// Score for playing the shape, whatever the outcome of the round.
+    pub fn shape_score(self) -> u32 {
         match self {
             Shape::Rock => 1,
             Shape::Paper => 2,
@@ -32,7 +41,8 @@ impl Shape {
         }
     }
 
-    fn outcome(self, opponent: Self) -> u32 {
+    /// Total score of a round where this shape was played against `opponent`.
+    pub fn outcome(self, opponent: Self) -> u32 {
         let match_result = match (self, opponent) {
             (a, b) if a == b.winning_shape() => 6,
             (a, b) if a == b => 3,
@@ -41,7 +51,8 @@ impl Shape {
         self.shape_score() + match_result
     }
 
-    fn winning_shape(self) -> Self {
+    /// Shape beating this one.
+    pub fn winning_shape(self) -> Self {
         match self {
             Shape::Rock => Shape::Paper,
             Shape::Paper => Shape::Scissors,
@@ -49,17 +60,37 @@ impl Shape {
         }
     }
 
-    fn losing_shape(self) -> Self {
+    /// Shape losing to this one.
+    pub fn losing_shape(self) -> Self {
         self.winning_shape().winning_shape()
     }
 }
 
-pub(super) const DAY2: Solution = Solution {
-    part1: |input| {
-        input
-            .lines()
-            .map(|line| {
-                let (opponent, response) = parse_line(line)?;
+pub struct Day2;
+
+impl Day for Day2 {
+    type Parsed<'a> = Vec<(Shape, u8)>;
+
+    fn number(&self) -> usize {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part1<'a>(
+        &self,
+        rounds: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        rounds
+            .iter()
+            .map(|&(opponent, response)| {
                 let response = match response {
                     b'X' => Shape::Rock,
                     b'Y' => Shape::Paper,
@@ -69,13 +100,17 @@ pub(super) const DAY2: Solution = Solution {
                 Ok(response.outcome(opponent))
             })
             .try_fold(0, |acc, score| Ok(acc + score?))
-            .map(|total_score| total_score.to_string())
-    },
-    part2: |input| {
-        input
-            .lines()
-            .map(|line| {
-                let (opponent, response) = parse_line(line)?;
+            .map(Answer::from)
+    }
+
+    fn part2<'a>(
+        &self,
+        rounds: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        rounds
+            .iter()
+            .map(|&(opponent, response)| {
                 let response = match response {
                     b'X' => opponent.losing_shape(),
                     b'Y' => opponent,
@@ -85,21 +120,33 @@ pub(super) const DAY2: Solution = Solution {
                 Ok(response.outcome(opponent))
             })
             .try_fold(0, |acc, score| Ok(acc + score?))
-            .map(|total_score| total_score.to_string())
-    },
-};
+            .map(Answer::from)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.choose(&["A", "B", "C"]),
+                    rng.choose(&["X", "Y", "Z"])
+                )
+            })
+            .collect()
+    }
+}
 
 #[cfg(test)]
 mod test {
     use crate::{lines, test};
     const EXAMPLE: &str = lines!("A Y" "B X" "C Z");
     test!(
-        DAY2.part1,
+        Day2.part1,
         example: EXAMPLE => 15,
         input: 10994,
     );
     test!(
-        DAY2.part2,
+        Day2.part2,
         example: EXAMPLE => 12,
         input: 12526,
     );
@@ -1,5 +1,9 @@
-use crate::Solution;
-use std::{collections::HashSet, iter};
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Params;
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
+use std::{collections::HashSet, error::Error, iter};
 
 fn priority(item: u8) -> Result<u32, &'static str> {
     match item {
@@ -9,12 +13,43 @@ fn priority(item: u8) -> Result<u32, &'static str> {
     }
 }
 
-pub(super) const DAY3: Solution = Solution {
-    part1: |input| {
+pub struct Day3;
+
+impl Day for Day3 {
+    type Parsed<'a> = Vec<&'a [u8]>;
+
+    fn number(&self) -> usize {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
         input
             .lines()
             .map(|line| {
-                let (a, b) = line.as_bytes().split_at(line.len() / 2);
+                for (i, item) in line.char_indices() {
+                    u8::try_from(item)
+                        .map_err(|_| "Expected an ASCII alphebetic character")
+                        .and_then(priority)
+                        .at(&line[i..i + item.len_utf8()])?;
+                }
+                Ok(line.as_bytes())
+            })
+            .collect()
+    }
+
+    fn part1<'a>(
+        &self,
+        rucksacks: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        rucksacks
+            .iter()
+            .map(|rucksack| {
+                let (a, b) = rucksack.split_at(rucksack.len() / 2);
                 let first_compartment: HashSet<u8> = a.iter().copied().collect();
                 b.iter()
                     .find(|item| first_compartment.contains(item))
@@ -22,33 +57,63 @@ pub(super) const DAY3: Solution = Solution {
                     .and_then(|&item| priority(item))
             })
             .try_fold(0, |acc, priority| Ok(acc + priority?))
-            .map(|sum| sum.to_string())
-    },
-    part2: |input| {
-        let mut lines = input.lines();
-        iter::from_fn(|| match (lines.next(), lines.next(), lines.next()) {
-            (Some(a), Some(b), Some(c)) => Some([a, b, c]),
-            _ => None,
-        })
-        .map(|rucksacks| {
-            let possible_choices: HashSet<u8> = rucksacks
-                .iter()
-                .map(|rucksack| rucksack.bytes().collect())
-                .reduce(|mut a: HashSet<u8>, b| {
-                    a.retain(|elem| b.contains(elem));
-                    a
-                })
-                .expect("Non-empty iterator");
-            if possible_choices.len() != 1 {
-                Err("Incorrect number of choices")
-            } else {
-                priority(*possible_choices.iter().next().unwrap())
+            .map(Answer::from)
+    }
+
+    fn part2<'a>(
+        &self,
+        rucksacks: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        rucksacks
+            .chunks_exact(3)
+            .map(|rucksacks| {
+                let possible_choices: HashSet<u8> = rucksacks
+                    .iter()
+                    .map(|rucksack| rucksack.iter().copied().collect())
+                    .reduce(|mut a: HashSet<u8>, b| {
+                        a.retain(|elem| b.contains(elem));
+                        a
+                    })
+                    .expect("Non-empty iterator");
+                if possible_choices.len() != 1 {
+                    Err("Incorrect number of choices")
+                } else {
+                    priority(*possible_choices.iter().next().unwrap())
+                }
+            })
+            .try_fold(0, |acc, priority| Ok(acc + priority?))
+            .map(Answer::from)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut input = String::new();
+        for _ in 0..size {
+            let mut items: Vec<u8> = (b'a'..=b'z').chain(b'A'..=b'Z').collect();
+            rng.shuffle(&mut items);
+            let (&badge, items) = items.split_first().unwrap();
+            // Every elf of the group picks from items of its own, so that only
+            // the badge is shared, and both compartments only share one item.
+            for items in items.chunks(17) {
+                let (&shared, items) = items.split_first().unwrap();
+                let (first, second) = items.split_at(items.len() / 2);
+                let length = rng.range(2..=12);
+                let mut first: Vec<u8> = [shared, badge]
+                    .into_iter()
+                    .chain((2..length).map(|_| *rng.choose(first)))
+                    .collect();
+                let mut second: Vec<u8> = iter::once(shared)
+                    .chain((1..length).map(|_| *rng.choose(second)))
+                    .collect();
+                rng.shuffle(&mut first);
+                rng.shuffle(&mut second);
+                input.extend(first.into_iter().chain(second).map(char::from));
+                input.push('\n');
             }
-        })
-        .try_fold(0, |acc, priority| Ok(acc + priority?))
-        .map(|sum| sum.to_string())
-    },
-};
+        }
+        input
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -62,12 +127,12 @@ mod test {
         "CrZsJsPPZsGzwwsLwLmpwMDw"
     );
     test!(
-        DAY3.part1,
+        Day3.part1,
         example: EXAMPLE => 157,
         input: 7766,
     );
     test!(
-        DAY3.part2,
+        Day3.part2,
         example: EXAMPLE => 70,
         input: 2415,
     );
@@ -1,35 +1,81 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Params;
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
 use std::error::Error;
 
-fn parse_line(line: &str) -> Result<(u8, u8, u8, u8), Box<dyn Error>> {
-    let (a, line) = line.split_once('-').ok_or("Missing first dash")?;
-    let (b, line) = line.split_once(',').ok_or("Missing first comma")?;
-    let (c, d) = line.split_once('-').ok_or("Missing second dash")?;
-    Ok((a.parse()?, b.parse()?, c.parse()?, d.parse()?))
+fn parse_line(line: &str) -> Result<(u8, u8, u8, u8), ParseError<'_>> {
+    let (a, line) = line.split_once('-').ok_or("Missing first dash").at(line)?;
+    let (b, line) = line.split_once(',').ok_or("Missing first comma").at(line)?;
+    let (c, d) = line.split_once('-').ok_or("Missing second dash").at(line)?;
+    Ok((
+        a.parse().at(a)?,
+        b.parse().at(b)?,
+        c.parse().at(c)?,
+        d.parse().at(d)?,
+    ))
 }
 
-pub(super) const DAY4: Solution = Solution {
-    part1: |input| {
+pub struct Day4;
+
+impl Day for Day4 {
+    type Parsed<'a> = Vec<(u8, u8, u8, u8)>;
+
+    fn number(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part1<'a>(
+        &self,
+        pairs: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
         let mut count = 0;
-        for line in input.lines() {
-            let (a, b, c, d) = parse_line(line)?;
+        for &(a, b, c, d) in pairs {
             if (a <= c && b >= d) || (c <= a && d >= b) {
                 count += 1;
             }
         }
-        Ok(count.to_string())
-    },
-    part2: |input| {
+        Ok(count.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        pairs: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
         let mut count = 0;
-        for line in input.lines() {
-            let (a, b, c, d) = parse_line(line)?;
+        for &(a, b, c, d) in pairs {
             if (a <= c && b >= c) || (c <= a && d >= a) {
                 count += 1;
             }
         }
-        Ok(count.to_string())
-    },
-};
+        Ok(count.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut range = || {
+            let start = rng.range(1..=99);
+            (start, rng.range(start..=99))
+        };
+        (0..size)
+            .map(|_| {
+                let (a, b) = range();
+                let (c, d) = range();
+                format!("{a}-{b},{c}-{d}\n")
+            })
+            .collect()
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -43,12 +89,12 @@ mod test {
         "2-6,4-8"
     );
     test!(
-        DAY4.part1,
+        Day4.part1,
         example: EXAMPLE => 2,
         input: 528,
     );
     test!(
-        DAY4.part2,
+        Day4.part2,
         example: EXAMPLE => 4,
         input: 881,
     );
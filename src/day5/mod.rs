@@ -1,16 +1,28 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::Params;
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
+use std::fmt::Write;
 use std::{collections::VecDeque, error::Error};
 
 fn parse_stacks<'a>(
     lines: &mut impl Iterator<Item = &'a str>,
-) -> Result<Vec<VecDeque<u8>>, &'static str> {
+) -> Result<Vec<VecDeque<u8>>, ParseError<'a>> {
     let mut stacks = Vec::new();
+    let mut previous = "";
     for line in &mut *lines {
+        previous = line;
         if line.contains('[') {
             let line = line.trim_end();
-            stacks.resize_with((line.len() + 1) / 4, VecDeque::new);
-            for (chunk, stack) in line.as_bytes().chunks(4).zip(&mut stacks) {
-                let c = *chunk.get(1).ok_or("Invalid chunk")?;
+            if stacks.len() < (line.len() + 1) / 4 {
+                stacks.resize_with((line.len() + 1) / 4, VecDeque::new);
+            }
+            for (i, (chunk, stack)) in line.as_bytes().chunks(4).zip(&mut stacks).enumerate() {
+                let c = *chunk
+                    .get(1)
+                    .ok_or("Invalid chunk")
+                    .at(line.get(i * 4..).unwrap_or(line))?;
                 if c != b' ' {
                     stack.push_front(c);
                 }
@@ -19,23 +31,47 @@ fn parse_stacks<'a>(
             break;
         }
     }
-    if lines.next() != Some("") {
-        return Err("Expected a blank line");
+    match lines.next() {
+        Some("") => Ok(stacks),
+        Some(line) => Err(ParseError::new(line, "Expected a blank line")),
+        None => Err(ParseError::new(
+            &previous[previous.len()..],
+            "Expected a blank line",
+        )),
     }
-    Ok(stacks)
 }
 
-fn parse_line(line: &str) -> Result<(usize, usize, usize), Box<dyn Error>> {
+/// Number of one of the `stacks` stacks, starting at 1.
+fn parse_stack(number: &str, stacks: usize) -> Result<usize, ParseError<'_>> {
+    let stack = number.parse().at(number)?;
+    if (1..=stacks).contains(&stack) {
+        Ok(stack)
+    } else {
+        Err(ParseError::new(
+            number,
+            format_args!("Expected a stack between 1 and {stacks}"),
+        ))
+    }
+}
+
+fn parse_line(line: &str, stacks: usize) -> Result<(usize, usize, usize), ParseError<'_>> {
     let line = line
         .strip_prefix("move ")
-        .ok_or("Expected line to start with move")?;
+        .ok_or("Expected line to start with move")
+        .at(line)?;
     let (count, rest) = line
         .split_once(" from ")
-        .ok_or("Expected line to have count")?;
+        .ok_or("Expected line to have count")
+        .at(line)?;
     let (from, to) = rest
         .split_once(" to ")
-        .ok_or("Expected line to have a rearrangment")?;
-    Ok((count.parse()?, from.parse()?, to.parse()?))
+        .ok_or("Expected line to have a rearrangment")
+        .at(rest)?;
+    Ok((
+        count.parse().at(count)?,
+        parse_stack(from, stacks)?,
+        parse_stack(to, stacks)?,
+    ))
 }
 
 fn get_stack_top(stacks: &[VecDeque<u8>]) -> Result<String, Box<dyn Error>> {
@@ -45,12 +81,54 @@ fn get_stack_top(stacks: &[VecDeque<u8>]) -> Result<String, Box<dyn Error>> {
         .collect()
 }
 
-pub(super) const DAY5: Solution = Solution {
-    part1: |input| {
+/// Starting stacks of crates along with the moves to rearrange them.
+pub struct Procedure {
+    stacks: Vec<VecDeque<u8>>,
+    moves: Vec<(usize, usize, usize)>,
+}
+
+impl Procedure {
+    /// Crates of each stack, from the bottom to the top.
+    pub fn stacks(&self) -> &[VecDeque<u8>] {
+        &self.stacks
+    }
+
+    /// Moves as the number of crates along with the 1-based stacks they are
+    /// moved from and to.
+    pub fn moves(&self) -> &[(usize, usize, usize)] {
+        &self.moves
+    }
+}
+
+pub struct Day5;
+
+impl Day for Day5 {
+    type Parsed<'a> = Procedure;
+
+    fn number(&self) -> usize {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
         let mut lines = input.lines();
-        let mut stacks = parse_stacks(&mut lines)?;
-        for line in lines {
-            let (count, from, to) = parse_line(line)?;
+        let stacks = parse_stacks(&mut lines)?;
+        let moves = lines
+            .map(|line| parse_line(line, stacks.len()))
+            .collect::<Result<_, _>>()?;
+        Ok(Procedure { stacks, moves })
+    }
+
+    fn part1<'a>(
+        &self,
+        procedure: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut stacks = procedure.stacks.clone();
+        for &(count, from, to) in &procedure.moves {
             for _ in 0..count {
                 let elem = stacks[from - 1]
                     .pop_back()
@@ -58,23 +136,72 @@ pub(super) const DAY5: Solution = Solution {
                 stacks[to - 1].push_back(elem);
             }
         }
-        get_stack_top(&stacks)
-    },
-    part2: |input| {
-        let mut lines = input.lines();
-        let mut stacks = parse_stacks(&mut lines)?;
-        for line in lines {
-            let (count, from, to) = parse_line(line)?;
-            let from = &mut stacks[from - 1];
-            let elems: Vec<u8> = from.drain(from.len() - count..).collect();
+        Ok(get_stack_top(&stacks)?.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        procedure: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut stacks = procedure.stacks.clone();
+        for &(count, from, to) in &procedure.moves {
+            let stack = &mut stacks[from - 1];
+            let start = stack
+                .len()
+                .checked_sub(count)
+                .ok_or_else(|| format!("Expected an element in {from}"))?;
+            let elems: Vec<u8> = stack.drain(start..).collect();
             stacks[to - 1].extend(elems);
         }
-        get_stack_top(&stacks)
-    },
-};
+        Ok(get_stack_top(&stacks)?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut stacks: Vec<Vec<u8>> = (0..rng.range(2..=9))
+            .map(|_| {
+                (0..rng.range(2..=8))
+                    .map(|_| *rng.choose(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ"))
+                    .collect()
+            })
+            .collect();
+        let mut input = String::new();
+        let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for level in (0..height).rev() {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| {
+                    stack
+                        .get(level)
+                        .map_or("   ".into(), |&c| format!("[{}]", char::from(c)))
+                })
+                .collect();
+            writeln!(input, "{}", row.join(" ").trim_end()).unwrap();
+        }
+        let numbers: Vec<String> = (1..=stacks.len()).map(|i| format!(" {i} ")).collect();
+        writeln!(input, "{}\n", numbers.join(" ").trim_end()).unwrap();
+        // Moves never empty a stack, so that every stack has a crate on top
+        // at the end.
+        for _ in 0..size {
+            let candidates: Vec<usize> =
+                (0..stacks.len()).filter(|&i| stacks[i].len() > 1).collect();
+            let from = *rng.choose(&candidates);
+            let to = (from + 1 + rng.below(stacks.len() - 1)) % stacks.len();
+            let count = rng.range(1..=stacks[from].len() as i64 - 1) as usize;
+            let split = stacks[from].len() - count;
+            let moved = stacks[from].split_off(split);
+            stacks[to].extend(moved);
+            writeln!(input, "move {count} from {} to {}", from + 1, to + 1).unwrap();
+        }
+        input
+    }
+}
 
 #[cfg(test)]
 mod test {
+    use super::Day5;
+    use crate::day::Day;
+    use crate::params::Params;
     use crate::{lines, test};
     const EXAMPLE: &str = lines!(
         "    [D]   "
@@ -88,13 +215,34 @@ mod test {
         "move 1 from 1 to 2"
     );
     test!(
-        DAY5.part1,
+        Day5.part1,
         example: EXAMPLE => "CMZ",
         input: "BZLVHBWQF",
     );
     test!(
-        DAY5.part2,
+        Day5.part2,
         example: EXAMPLE => "MCD",
         input: "TDGJQTZSL",
     );
+
+    #[test]
+    fn unknown_stack() {
+        let input = lines!("[A]" " 1" "" "move 1 from 1 to 2");
+        let error = Day5.parse(input).err().unwrap().locate(input);
+        assert_eq!((error.line, error.column), (4, 18));
+        assert_eq!(error.message, "Expected a stack between 1 and 1");
+    }
+
+    #[test]
+    fn too_many_crates() {
+        let input = lines!("[A] [B]" " 1   2" "" "move 2 from 1 to 2");
+        let procedure = Day5.parse(input).unwrap();
+        let params = Params::default();
+        for part in [
+            Day5.part1(&procedure, &params),
+            Day5.part2(&procedure, &params),
+        ] {
+            assert_eq!(part.err().unwrap().to_string(), "Expected an element in 1");
+        }
+    }
 }
@@ -1,34 +1,122 @@
-use crate::Solution;
-use std::{collections::HashSet, error::Error};
-
-fn solution<const SIZE: usize>(input: &str) -> Result<String, Box<dyn Error>> {
-    let pos = input
-        .as_bytes()
-        .windows(SIZE)
-        .position(|window| window.iter().copied().collect::<HashSet<_>>().len() == SIZE)
-        .ok_or("No valid start-of-packet marker found")?;
-    Ok((pos + SIZE).to_string())
+use crate::answer::Answer;
+use crate::day::{Day, Stream};
+use crate::params::Params;
+use crate::parse::ParseError;
+use crate::rng::Rng;
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::io::{BufRead, Read};
+
+const NOT_FOUND: &str = "No valid start-of-packet marker found";
+
+fn is_marker(window: &[u8]) -> bool {
+    window.iter().copied().collect::<HashSet<_>>().len() == window.len()
+}
+
+fn solution<const SIZE: usize>(input: &[u8]) -> Result<usize, Box<dyn Error>> {
+    let pos = input.windows(SIZE).position(is_marker).ok_or(NOT_FOUND)?;
+    Ok(pos + SIZE)
+}
+
+/// Same as [`solution`], reading only up to the end of the marker.
+fn stream_solution<const SIZE: usize>(input: &mut dyn BufRead) -> Result<usize, Box<dyn Error>> {
+    let mut window = VecDeque::with_capacity(SIZE);
+    for (i, byte) in (1..).zip(input.bytes()) {
+        let byte = byte?;
+        if matches!(byte, b'\r' | b'\n') {
+            break;
+        }
+        if window.len() == SIZE {
+            window.pop_front();
+        }
+        window.push_back(byte);
+        if window.len() == SIZE && is_marker(window.make_contiguous()) {
+            return Ok(i);
+        }
+    }
+    Err(NOT_FOUND.into())
+}
+
+pub struct Day6;
+
+impl Day for Day6 {
+    type Parsed<'a> = &'a [u8];
+
+    fn number(&self) -> usize {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        Ok(input.trim_end().as_bytes())
+    }
+
+    fn part1<'a>(
+        &self,
+        input: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        Ok(solution::<4>(input)?.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        input: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        Ok(solution::<14>(input)?.into())
+    }
+
+    fn streaming(&self) -> Option<&dyn Stream> {
+        Some(self)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut letters: Vec<u8> = (b'a'..=b'z').collect();
+        let mut input: String = (0..size)
+            .map(|_| char::from(*rng.choose(&letters)))
+            .collect();
+        // End with a marker in case none appeared by chance.
+        rng.shuffle(&mut letters);
+        input.extend(letters[..14].iter().copied().map(char::from));
+        input.push('\n');
+        input
+    }
 }
 
-pub(super) const DAY6: Solution = Solution {
-    part1: solution::<4>,
-    part2: solution::<14>,
-};
+impl Stream for Day6 {
+    fn stream_part1(&self, input: &mut dyn BufRead, _: &Params) -> Result<Answer, Box<dyn Error>> {
+        Ok(stream_solution::<4>(input)?.into())
+    }
+
+    fn stream_part2(&self, input: &mut dyn BufRead, _: &Params) -> Result<Answer, Box<dyn Error>> {
+        Ok(stream_solution::<14>(input)?.into())
+    }
+}
 
 #[cfg(test)]
 mod test {
     use crate::test;
     test!(
-        DAY6.part1,
+        Day6.part1,
         example1: "mjqjpqmgbljsphdztnvjfqwrcgsmlb" => 7,
         example2: "bvwbjplbgvbhsrlpgdmjqwftvncz" => 5,
         example3: "nppdvjthqldpwncqszvftbrmjlhg" => 6,
         example4: "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg" => 10,
         example5: "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw" => 11,
         input: 1965,
+        fn no_marker() {
+            let input = crate::input::normalize("abcabc");
+            let parsed = Day6.parse(&input).unwrap();
+            assert_eq!(parsed, b"abcabc");
+            assert!(Day6.part1(&parsed, &crate::params::Params::default()).is_err());
+        }
     );
     test!(
-        DAY6.part2,
+        Day6.part2,
         example1: "mjqjpqmgbljsphdztnvjfqwrcgsmlb" => 19,
         example2: "bvwbjplbgvbhsrlpgdmjqwftvncz" => 23,
         example3: "nppdvjthqldpwncqszvftbrmjlhg" => 23,
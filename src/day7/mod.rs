@@ -1,46 +1,69 @@
-use crate::Solution;
-use std::{cell::Cell, collections::HashMap, error::Error};
+use crate::answer::Answer;
+use crate::day::Day;
+use crate::params::{Param, Params};
+use crate::parse::{At, ParseError};
+use crate::rng::Rng;
+use std::fmt::Write;
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    error::Error,
+};
 
-struct Filesystem<'a> {
+/// Directory tree reconstructed from a terminal session.
+pub struct Filesystem<'a> {
     directories: Vec<Directory<'a>>,
 }
 
 impl<'a> Filesystem<'a> {
-    fn parse(input: &'a str) -> Result<Self, Box<dyn Error>> {
+    /// Replays `cd` and `ls` commands along with their output.
+    pub fn parse(input: &'a str) -> Result<Self, ParseError<'a>> {
         let mut fs = Self {
             directories: vec![Directory::new(None)],
         };
-        let mut current_directory = !0;
+        let mut current_directory = None;
         for line in input.lines() {
             if line == "$ cd /" {
-                current_directory = 0;
-            } else if line == "$ cd .." {
-                current_directory = fs.directories[current_directory]
-                    .parent_dir
-                    .ok_or("Cannot use cd .. for root directory")?;
+                current_directory = Some(0);
+                continue;
             } else if line == "$ ls" {
-                // Do nothing
+                continue;
+            }
+            let current = current_directory
+                .ok_or("Expected `$ cd /` before any other command or output")
+                .at(line)?;
+            if line == "$ cd .." {
+                current_directory = Some(
+                    fs.directories[current]
+                        .parent_dir
+                        .ok_or("Cannot use cd .. for root directory")
+                        .at(line)?,
+                );
             } else if let Some(path) = line.strip_prefix("$ cd ") {
-                match fs.directories[current_directory]
+                match fs.directories[current]
                     .files
                     .get(path)
-                    .ok_or("Unrecognized directory")?
+                    .ok_or("Unrecognized directory")
+                    .at(path)?
                 {
-                    &Kind::Directory { id } => current_directory = id,
-                    _ => return Err("Expected a directory".into()),
+                    &Kind::Directory { id } => current_directory = Some(id),
+                    _ => return Err(ParseError::new(path, "Expected a directory")),
                 }
             } else if let Some(path) = line.strip_prefix("dir ") {
                 let id = fs.directories.len();
-                fs.directories.push(Directory::new(Some(current_directory)));
-                fs.directories[current_directory]
+                fs.directories.push(Directory::new(Some(current)));
+                fs.directories[current]
                     .files
                     .insert(path, Kind::Directory { id });
             } else {
-                let (size, name) = line.split_once(' ').ok_or("Expected a valid line")?;
-                fs.directories[current_directory].files.insert(
+                let (size, name) = line
+                    .split_once(' ')
+                    .ok_or("Expected a valid line")
+                    .at(line)?;
+                fs.directories[current].files.insert(
                     name,
                     Kind::File {
-                        size: size.parse()?,
+                        size: size.parse().at(size)?,
                     },
                 );
             }
@@ -49,12 +72,22 @@ impl<'a> Filesystem<'a> {
         Ok(fs)
     }
 
+    /// Total size of every directory, starting with the root directory.
+    pub fn directory_sizes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.directories.iter().map(|dir| dir.total_size.get())
+    }
+
+    /// Total size of the root directory.
+    pub fn used_space(&self) -> u32 {
+        self.directories[0].total_size.get()
+    }
+
     fn compute_total_size(&self, index: usize) -> u32 {
         let directory = &self.directories[index];
         let sum = directory
             .files
-            .iter()
-            .map(|(_, kind)| match *kind {
+            .values()
+            .map(|kind| match *kind {
                 Kind::Directory { id } => self.compute_total_size(id),
                 Kind::File { size } => size,
             })
@@ -85,36 +118,153 @@ enum Kind {
     Directory { id: usize },
 }
 
-pub(super) const DAY7: Solution = Solution {
-    part1: |input| {
-        let fs = Filesystem::parse(input)?;
+const SMALL_DIRECTORY: Param = Param {
+    name: "small_directory",
+    default: 100_000,
+    description: "Largest size of directories summed in part 1",
+};
+const DISK_SPACE: Param = Param {
+    name: "disk_space",
+    default: 70_000_000,
+    description: "Total size of the filesystem",
+};
+const REQUIRED_SPACE: Param = Param {
+    name: "required_space",
+    default: 30_000_000,
+    description: "Unused space needed for the update",
+};
+
+/// Random name unique among `names`, with an extension for files.
+fn unique_name(rng: &mut Rng, names: &mut HashSet<String>, extension: bool) -> String {
+    loop {
+        let mut name: String = (0..rng.range(1..=8))
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect();
+        if extension && rng.bool() {
+            name.push('.');
+            name.extend((0..3).map(|_| char::from(b'a' + rng.below(26) as u8)));
+        }
+        if names.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Lists a directory, then visits its subdirectories one after the other.
+fn write_session(
+    input: &mut String,
+    directory: usize,
+    directories: &[Vec<(String, usize)>],
+    files: &[Vec<(String, u32)>],
+) {
+    input.push_str("$ ls\n");
+    for (name, _) in &directories[directory] {
+        writeln!(input, "dir {name}").unwrap();
+    }
+    for (name, size) in &files[directory] {
+        writeln!(input, "{size} {name}").unwrap();
+    }
+    for (name, subdirectory) in &directories[directory] {
+        writeln!(input, "$ cd {name}").unwrap();
+        write_session(input, *subdirectory, directories, files);
+        input.push_str("$ cd ..\n");
+    }
+}
+
+pub struct Day7;
+
+impl Day for Day7 {
+    type Parsed<'a> = Filesystem<'a>;
+
+    fn number(&self) -> usize {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[SMALL_DIRECTORY, DISK_SPACE, REQUIRED_SPACE]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        Filesystem::parse(input)
+    }
+
+    fn part1<'a>(
+        &self,
+        fs: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let small_directory = params.get::<u32>(&SMALL_DIRECTORY)?;
         Ok(fs
-            .directories
-            .iter()
-            .map(|dir| dir.total_size.get())
-            .filter(|&total_size| total_size <= 100_000)
+            .directory_sizes()
+            .filter(|&total_size| total_size <= small_directory)
             .sum::<u32>()
-            .to_string())
-    },
-    part2: |input| {
-        let fs = Filesystem::parse(input)?;
-        let necessary_to_free = 30_000_000_u32
+            .into())
+    }
+
+    fn part2<'a>(
+        &self,
+        fs: &Self::Parsed<'a>,
+        params: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let necessary_to_free = params
+            .get::<u32>(&REQUIRED_SPACE)?
             .checked_sub(
-                70_000_000_u32
-                    .checked_sub(fs.directories[0].total_size.get())
+                params
+                    .get::<u32>(&DISK_SPACE)?
+                    .checked_sub(fs.used_space())
                     .ok_or("Root directory larger than total available space")?,
             )
             .ok_or("No directory needs to be deleted")?;
         Ok(fs
-            .directories
-            .iter()
-            .map(|dir| dir.total_size.get())
+            .directory_sizes()
             .filter(|&total_size| total_size >= necessary_to_free)
             .min()
             .ok_or("Couldn't find a small enough directory")?
-            .to_string())
-    },
-};
+            .into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let mut names = vec![HashSet::new(); size];
+        // Every directory but the root is placed in one of those before it.
+        let mut directories = vec![Vec::new(); size];
+        for directory in 1..size {
+            let parent = rng.below(directory);
+            let name = unique_name(rng, &mut names[parent], false);
+            directories[parent].push((name, directory));
+        }
+        let weights: Vec<Vec<(String, u32)>> = names
+            .iter_mut()
+            .map(|names| {
+                (0..rng.range(1..=4))
+                    .map(|_| (unique_name(rng, names, true), rng.range(1..=1_000) as u32))
+                    .collect()
+            })
+            .collect();
+        // Scale file sizes so that the disk is mostly full, leaving part 2
+        // something to delete.
+        let used_space = rng.range(45_000_000..=65_000_000) as u64;
+        let total_weight: u64 = weights.iter().flatten().map(|&(_, w)| u64::from(w)).sum();
+        let files: Vec<Vec<(String, u32)>> = weights
+            .into_iter()
+            .map(|files| {
+                files
+                    .into_iter()
+                    .map(|(name, weight)| {
+                        let size = used_space * u64::from(weight) / total_weight;
+                        (name, size.max(1) as u32)
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut input = "$ cd /\n".to_string();
+        write_session(&mut input, 0, &directories, &files);
+        input
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -145,13 +295,24 @@ mod test {
         "7214296 k"
     );
     test!(
-        DAY7.part1,
+        Day7.part1,
         example: EXAMPLE => 95437,
         input: 1334506,
     );
     test!(
-        DAY7.part2,
+        Day7.part2,
         example: EXAMPLE => 24933642,
         input: 7421137,
     );
+
+    #[test]
+    fn missing_root() {
+        let input = lines!("$ ls" "dir a");
+        let error = super::Filesystem::parse(input).err().unwrap().locate(input);
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(
+            error.message,
+            "Expected `$ cd /` before any other command or output"
+        );
+    }
 }
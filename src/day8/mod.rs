@@ -1,72 +1,103 @@
-use crate::{grid::Grid, Solution};
-use std::collections::HashSet;
+use crate::{
+    answer::Answer,
+    day::Day,
+    grid::{Direction, Grid},
+    params::Params,
+    parse::ParseError,
+    rng::Rng,
+};
+use std::error::Error;
 
-fn scan_valid_trees(
-    valid_trees: &mut HashSet<(usize, usize)>,
-    grid: &Grid<'_>,
-    mut positions: impl Iterator<Item = (usize, usize)>,
+fn scan_valid_trees<'a>(
+    valid_trees: &mut Grid<bool>,
+    mut trees: impl Iterator<Item = ((usize, usize), &'a u8)>,
 ) {
-    let position @ (x, y) = positions.next().expect("non-empty list");
-    valid_trees.insert(position);
-    let mut current_tree = grid.at(x, y).unwrap();
-    for position @ (x, y) in positions {
-        let new_tree = grid.at(x, y).unwrap();
+    let ((x, y), mut current_tree) = trees.next().expect("non-empty list");
+    valid_trees.set(x, y, true);
+    for ((x, y), new_tree) in trees {
         if new_tree > current_tree {
             current_tree = new_tree;
-            valid_trees.insert(position);
+            valid_trees.set(x, y, true);
         }
     }
 }
 
-fn scan_score(mut trees: impl Iterator<Item = u8>) -> u32 {
-    let first_tree = trees.next().expect("non-empty list");
+/// Number of trees seen from a tree of the given height looking along `trees`.
+fn scan_score<'a>(height: u8, trees: impl Iterator<Item = ((usize, usize), &'a u8)>) -> u32 {
     let mut score = 0;
-    for new_tree in trees {
+    for (_, &new_tree) in trees {
         score += 1;
-        if new_tree >= first_tree {
+        if new_tree >= height {
             break;
         }
     }
     score
 }
 
-pub(super) const DAY8: Solution = Solution {
-    part1: |input| {
-        let grid = Grid::parse(input)?;
-        let mut valid_trees = HashSet::new();
+pub struct Day8;
+
+impl Day for Day8 {
+    type Parsed<'a> = Grid<u8>;
+
+    fn number(&self) -> usize {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Treetop Tree House"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        Grid::parse(input)
+    }
+
+    fn part1<'a>(
+        &self,
+        grid: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        let mut valid_trees = Grid::new(grid.width(), grid.height(), false);
         for x in 0..grid.width() {
-            scan_valid_trees(&mut valid_trees, &grid, (0..grid.height()).map(|y| (x, y)));
-            scan_valid_trees(
-                &mut valid_trees,
-                &grid,
-                (0..grid.height()).rev().map(|y| (x, y)),
-            );
+            scan_valid_trees(&mut valid_trees, grid.column(x));
+            scan_valid_trees(&mut valid_trees, grid.column(x).rev());
         }
         for y in 0..grid.height() {
-            scan_valid_trees(&mut valid_trees, &grid, (0..grid.width()).map(|x| (x, y)));
-            scan_valid_trees(
-                &mut valid_trees,
-                &grid,
-                (0..grid.width()).rev().map(|x| (x, y)),
-            );
+            scan_valid_trees(&mut valid_trees, grid.row(y));
+            scan_valid_trees(&mut valid_trees, grid.row(y).rev());
         }
-        Ok(valid_trees.len().to_string())
-    },
-    part2: |input| {
-        let grid = Grid::parse(input)?;
+        Ok(valid_trees.iter().filter(|&&valid| valid).count().into())
+    }
+
+    fn part2<'a>(
+        &self,
+        grid: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
         let mut max_score = 0;
         for x in 1..grid.width() - 1 {
             for y in 1..grid.height() - 1 {
-                let up_score = scan_score((0..=y).rev().map(|y| grid.at(x, y).unwrap()));
-                let down_score = scan_score((y..grid.height()).map(|y| grid.at(x, y).unwrap()));
-                let left_score = scan_score((0..=x).rev().map(|x| grid.at(x, y).unwrap()));
-                let right_score = scan_score((x..grid.width()).map(|x| grid.at(x, y).unwrap()));
-                max_score = max_score.max(up_score * down_score * left_score * right_score);
+                let height = *grid.get(x, y).unwrap();
+                let score = Direction::ORTHOGONAL
+                    .iter()
+                    .map(|&direction| scan_score(height, grid.ray((x, y), direction)))
+                    .product();
+                max_score = max_score.max(score);
             }
         }
-        Ok(max_score.to_string())
-    },
-};
+        Ok(max_score.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| char::from(b'0' + rng.below(10) as u8))
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -79,12 +110,12 @@ mod test {
         "35390"
     );
     test!(
-        DAY8.part1,
+        Day8.part1,
         example: EXAMPLE => 21,
         input: 1695,
     );
     test!(
-        DAY8.part2,
+        Day8.part2,
         example: EXAMPLE => 8,
         input: 287040,
     );
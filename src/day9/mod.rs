@@ -1,4 +1,9 @@
-use crate::Solution;
+use crate::answer::Answer;
+use crate::day::{Day, Stream};
+use crate::params::Params;
+use crate::parse::{self, At, ParseError};
+use crate::rng::Rng;
+use std::io::BufRead;
 use std::{collections::HashSet, error::Error};
 
 struct Simulation<const N: usize> {
@@ -35,28 +40,103 @@ impl<const N: usize> Simulation<N> {
     }
 }
 
-fn solve<const N: usize>(input: &str) -> Result<String, Box<dyn Error>> {
+fn parse_line(line: &str) -> Result<((i32, i32), u32), ParseError<'_>> {
+    let (direction, count) = line
+        .split_once(' ')
+        .ok_or("Expected a valid line")
+        .at(line)?;
+    let offset = match direction {
+        "L" => (-1, 0),
+        "R" => (1, 0),
+        "U" => (0, -1),
+        "D" => (0, 1),
+        _ => return Err(ParseError::new(direction, "Unrecognized direction")),
+    };
+    Ok((offset, count.parse().at(count)?))
+}
+
+fn solve<const N: usize>(moves: &[((i32, i32), u32)]) -> Result<usize, Box<dyn Error>> {
+    let mut simulation = Simulation::<N>::new();
+    for &(offset, count) in moves {
+        for _ in 0..count {
+            simulation.move_by(offset);
+        }
+    }
+    Ok(simulation.visited_positions.len())
+}
+
+/// Same as [`solve`], applying every move as soon as its line is read.
+fn stream_solve<const N: usize>(input: &mut dyn BufRead) -> Result<usize, Box<dyn Error>> {
     let mut simulation = Simulation::<N>::new();
-    for line in input.lines() {
-        let (direction, count) = line.split_once(' ').ok_or("Expected a valid line")?;
-        let offset = match direction {
-            "L" => (-1, 0),
-            "R" => (1, 0),
-            "U" => (0, -1),
-            "D" => (0, 1),
-            _ => return Err(format!("Unrecognized direction {direction}").into()),
-        };
-        for _ in 0u32..count.parse()? {
+    parse::for_each_line(input, |line| {
+        let (offset, count) = parse_line(line)?;
+        for _ in 0..count {
             simulation.move_by(offset);
         }
+        Ok(())
+    })?;
+    Ok(simulation.visited_positions.len())
+}
+
+pub struct Day9;
+
+impl Day for Day9 {
+    type Parsed<'a> = Vec<((i32, i32), u32)>;
+
+    fn number(&self) -> usize {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Rope Bridge"
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>, ParseError<'a>> {
+        input.lines().map(parse_line).collect()
+    }
+
+    fn part1<'a>(
+        &self,
+        moves: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        Ok(solve::<2>(moves)?.into())
+    }
+
+    fn part2<'a>(
+        &self,
+        moves: &Self::Parsed<'a>,
+        _: &Params,
+    ) -> Result<Answer, Box<dyn Error + 'a>> {
+        Ok(solve::<10>(moves)?.into())
+    }
+
+    fn streaming(&self) -> Option<&dyn Stream> {
+        Some(self)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.choose(&["L", "R", "U", "D"]),
+                    rng.range(1..=20)
+                )
+            })
+            .collect()
     }
-    Ok(simulation.visited_positions.len().to_string())
 }
 
-pub(super) const DAY9: Solution = Solution {
-    part1: solve::<2>,
-    part2: solve::<10>,
-};
+impl Stream for Day9 {
+    fn stream_part1(&self, input: &mut dyn BufRead, _: &Params) -> Result<Answer, Box<dyn Error>> {
+        Ok(stream_solve::<2>(input)?.into())
+    }
+
+    fn stream_part2(&self, input: &mut dyn BufRead, _: &Params) -> Result<Answer, Box<dyn Error>> {
+        Ok(stream_solve::<10>(input)?.into())
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -72,12 +152,12 @@ mod test {
         "R 2"
     );
     test!(
-        DAY9.part1,
+        Day9.part1,
         example: EXAMPLE => 13,
         input: 6256,
     );
     test!(
-        DAY9.part2,
+        Day9.part2,
         example1: EXAMPLE => 1,
         example2: lines!(
             "R 5"
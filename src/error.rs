@@ -0,0 +1,52 @@
+use advent_of_code_2022::report::{Failure, FailureKind};
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::process::ExitCode;
+
+/// Error returned by the command line interface, determining its exit code.
+pub enum CliError {
+    Io(io::Error),
+    Usage(String),
+    Input(String),
+    Failed(Vec<Failure>),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(match self {
+            Self::Io(_) => 1,
+            Self::Usage(_) => 2,
+            Self::Input(_) => 3,
+            Self::Failed(failures) => failures
+                .iter()
+                .map(|failure| failure.kind)
+                .max()
+                .map_or(1, FailureKind::exit_code),
+        })
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Usage(message) | Self::Input(message) => f.write_str(message),
+            Self::Failed(failures) => {
+                f.write_str("Failed: ")?;
+                for (i, failure) in failures.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{failure}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
@@ -1,18 +1,116 @@
-pub struct Grid<'a> {
-    input: &'a [u8],
+use crate::parse::{At, ParseError};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter, Write};
+use std::iter;
+
+/// Direction towards a neighboring cell, with up being towards row 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// Directions of the 4-neighborhood.
+    pub const ORTHOGONAL: [Self; 4] = [Self::Up, Self::Down, Self::Left, Self::Right];
+
+    /// Directions of the 8-neighborhood.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::Down,
+        Self::Left,
+        Self::Right,
+        Self::UpLeft,
+        Self::UpRight,
+        Self::DownLeft,
+        Self::DownRight,
+    ];
+
+    /// Change in `x` and `y` when moving one cell in the direction.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+            Self::UpLeft => (-1, -1),
+            Self::UpRight => (1, -1),
+            Self::DownLeft => (-1, 1),
+            Self::DownRight => (1, 1),
+        }
+    }
+}
+
+/// Rectangular grid of cells stored row by row, with `(0, 0)` at the top left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
     width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Grid of the given dimensions with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Grid of the bytes of each line.
+    pub fn parse(input: &str) -> Result<Self, ParseError<'_>> {
+        Self::parse_with(input, Ok::<_, &str>)
+    }
 }
 
-impl<'a> Grid<'a> {
-    pub fn parse(input: &'a str) -> Result<Self, &'static str> {
+impl<T> Grid<T> {
+    /// Grid with a line of input per row, mapping each byte to a cell with
+    /// `cell`, whose errors point at the byte it rejected. Lines may end with
+    /// `\r\n` and the last one needn't end at all, but they must all have the
+    /// same width.
+    pub fn parse_with<'a, E: std::fmt::Display>(
+        input: &'a str,
+        mut cell: impl FnMut(u8) -> Result<T, E>,
+    ) -> Result<Self, ParseError<'a>> {
         let width = input
             .lines()
             .next()
-            .ok_or("Expected non-empty input")?
+            .ok_or("Expected non-empty input")
+            .at(input)?
             .len();
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for line in input.lines() {
+            if line.len() != width {
+                let span = line.get(width..).unwrap_or(&line[line.len()..]);
+                return Err(ParseError::new(
+                    span,
+                    format_args!(
+                        "Expected row {} to have {width} cells like the first one, found {}",
+                        height + 1,
+                        line.len()
+                    ),
+                ));
+            }
+            for (i, byte) in line.bytes().enumerate() {
+                cells.push(cell(byte).at(line.get(i..=i).unwrap_or(line))?);
+            }
+            height += 1;
+        }
         Ok(Self {
-            input: input.as_bytes(),
+            cells,
             width,
+            height,
         })
     }
 
@@ -21,25 +119,351 @@ impl<'a> Grid<'a> {
     }
 
     pub fn height(&self) -> usize {
-        self.input.len() / (self.width + 1)
+        self.height
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Cell at the given position, or `None` outside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.cells.get(self.index(x, y)?)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        let index = self.index(x, y)?;
+        self.cells.get_mut(index)
     }
 
-    pub fn at(&self, x: usize, y: usize) -> Option<u8> {
-        self.input
-            .get(y.checked_mul(self.width + 1)?..)?
-            .get(..self.width)?
-            .get(x)
-            .copied()
+    /// Replaces the cell at the given position, returning the previous one,
+    /// or `None` outside of the grid, in which case nothing changes.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> Option<T> {
+        self.get_mut(x, y)
+            .map(|cell| std::mem::replace(cell, value))
     }
 
-    pub fn find(&self, c: u8) -> Option<(usize, usize)> {
-        for x in 0..self.width() {
-            for y in 0..self.height() {
-                if self.at(x, y).unwrap() == c {
-                    return Some((x, y));
+    /// Cells row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    fn cell(&self, position: (usize, usize)) -> Option<((usize, usize), &T)> {
+        Some((position, self.get(position.0, position.1)?))
+    }
+
+    /// Position of the cell next to the given one in `direction`, if it is
+    /// inside the grid.
+    pub fn step(&self, (x, y): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (offset_x, offset_y) = direction.offset();
+        let position = (
+            x.checked_add_signed(offset_x)?,
+            y.checked_add_signed(offset_y)?,
+        );
+        self.index(position.0, position.1).map(|_| position)
+    }
+
+    /// Cells next to the given one in the given directions, with their
+    /// positions.
+    pub fn neighbors(
+        &self,
+        position: (usize, usize),
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        directions
+            .iter()
+            .filter_map(move |&direction| self.cell(self.step(position, direction)?))
+    }
+
+    /// Cells sharing a side with the given one.
+    pub fn neighbors4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.neighbors(position, &Direction::ORTHOGONAL)
+    }
+
+    /// Cells sharing a side or a corner with the given one.
+    pub fn neighbors8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.neighbors(position, &Direction::ALL)
+    }
+
+    /// Cells from the one after the given position in `direction` up to the
+    /// edge of the grid, with their positions.
+    pub fn ray(
+        &self,
+        position: (usize, usize),
+        direction: Direction,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        iter::successors(self.step(position, direction), move |&position| {
+            self.step(position, direction)
+        })
+        .map_while(|position| self.cell(position))
+    }
+
+    /// Cells of row `y` from left to right, with their positions.
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = ((usize, usize), &T)> + '_ {
+        let width = if y < self.height { self.width } else { 0 };
+        (0..width).filter_map(move |x| self.cell((x, y)))
+    }
+
+    /// Cells of column `x` from top to bottom, with their positions.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = ((usize, usize), &T)> + '_ {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).filter_map(move |y| self.cell((x, y)))
+    }
+
+    /// Position of the first cell equal to `value`, going column by column.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        (0..self.width)
+            .flat_map(|x| (0..self.height).map(move |y| (x, y)))
+            .find(|&(x, y)| self.get(x, y) == Some(value))
+    }
+}
+
+/// Color of an overlay, drawn with ANSI escape codes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        match self {
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+        }
+    }
+}
+
+/// Cells highlighted when rendering a grid, such as a path or visited cells.
+pub struct Overlay {
+    cells: HashSet<(usize, usize)>,
+    marker: Option<char>,
+    color: Option<Color>,
+}
+
+impl Overlay {
+    /// Overlay over the given cells, which keep their character unless a
+    /// marker is set.
+    pub fn new(cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+            marker: None,
+            color: None,
+        }
+    }
+
+    /// Draws the cells with `marker` instead of their character.
+    pub fn marker(mut self, marker: char) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Draws the cells in `color`, when colors are enabled.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Text rendering of a grid, one line per row, created by [`Grid::render`].
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    cell: Box<dyn Fn(&T) -> char + 'a>,
+    overlays: Vec<Overlay>,
+    colors: bool,
+}
+
+impl<T> Render<'_, T> {
+    /// Adds an overlay, drawn above the cells and the previous overlays.
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Whether overlay colors are written as ANSI escape codes, which is off
+    /// by default so that the rendering can be written anywhere.
+    pub fn colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+}
+
+impl<T> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for y in 0..self.grid.height() {
+            if y != 0 {
+                f.write_char('\n')?;
+            }
+            for ((x, y), cell) in self.grid.row(y) {
+                let mut c = (self.cell)(cell);
+                let mut color = None;
+                for overlay in self.overlays.iter().filter(|o| o.cells.contains(&(x, y))) {
+                    c = overlay.marker.unwrap_or(c);
+                    color = overlay.color.or(color);
+                }
+                match color {
+                    Some(color) if self.colors => write!(f, "\x1b[{}m{c}\x1b[0m", color.code())?,
+                    _ => f.write_char(c)?,
                 }
             }
         }
-        None
+        Ok(())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Renders the grid as text, drawing each cell with `cell`.
+    pub fn render<'a>(&'a self, cell: impl Fn(&T) -> char + 'a) -> Render<'a, T> {
+        Render {
+            grid: self,
+            cell: Box::new(cell),
+            overlays: Vec::new(),
+            colors: false,
+        }
+    }
+}
+
+/// Lines of the grid as they were parsed.
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(|&c| char::from(c)).fmt(f)
+    }
+}
+
+/// Set cells drawn as `#` and others as `.`.
+impl Display for Grid<bool> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.render(|&set| if set { '#' } else { '.' }).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Color, Direction, Grid, Overlay};
+    use crate::lines;
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse(lines!("ab" "cd" "ef")).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.get(1, 2), Some(&b'f'));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.find(&b'd'), Some((1, 1)));
+    }
+
+    #[test]
+    fn layouts() {
+        let expected = Grid::parse(lines!("ab" "cd")).unwrap();
+        assert_eq!(Grid::parse("ab\r\ncd\r\n").unwrap(), expected);
+        assert_eq!(Grid::parse("ab\ncd").unwrap(), expected);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let input = lines!("abc" "de" "fgh");
+        let error = Grid::parse(input).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Expected row 2 to have 3 cells like the first one, found 2"
+        );
+        let error = error.locate(input);
+        assert_eq!((error.line, error.column), (2, 3));
+        let input = lines!("ab" "cde");
+        let error = Grid::parse(input).unwrap_err().locate(input);
+        assert_eq!((error.line, error.column, error.width), (2, 3, 1));
+    }
+
+    #[test]
+    fn parse_with() {
+        let input = lines!("12" "3x");
+        let error = Grid::parse_with(input, |c| {
+            char::from(c).to_digit(10).ok_or("Expected a digit")
+        })
+        .unwrap_err()
+        .locate(input);
+        assert_eq!((error.line, error.column), (2, 2));
+        let grid = Grid::parse_with(lines!("12" "34"), |c| {
+            char::from(c).to_digit(10).ok_or("Expected a digit")
+        })
+        .unwrap();
+        assert_eq!(grid.get(0, 1), Some(&3));
+    }
+
+    #[test]
+    fn mutate() {
+        let mut grid = Grid::new(3, 2, false);
+        assert_eq!(grid.set(2, 1, true), Some(false));
+        assert_eq!(grid.set(3, 1, true), None);
+        *grid.get_mut(0, 0).unwrap() = true;
+        assert_eq!(grid.get(2, 1), Some(&true));
+        assert_eq!(grid.find(&true), Some((0, 0)));
+        assert_eq!(grid.iter().filter(|&&cell| cell).count(), 2);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::parse(lines!("abc" "def" "ghi")).unwrap();
+        let cells = |neighbors: Vec<((usize, usize), &u8)>| -> String {
+            neighbors.into_iter().map(|(_, &c)| char::from(c)).collect()
+        };
+        assert_eq!(cells(grid.neighbors4((1, 1)).collect()), "bhdf");
+        assert_eq!(cells(grid.neighbors8((1, 1)).collect()), "bhdfacgi");
+        assert_eq!(cells(grid.neighbors4((0, 0)).collect()), "db");
+        assert_eq!(cells(grid.neighbors8((2, 2)).collect()), "fhe");
+        assert_eq!(grid.neighbors4((2, 0)).next(), Some(((2, 1), &b'f')));
+        assert_eq!(grid.step((0, 2), Direction::Down), None);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = Grid::parse(lines!("abc" "def" "ghi")).unwrap();
+        let cells = |cells: Vec<((usize, usize), &u8)>| -> String {
+            cells.into_iter().map(|(_, &c)| char::from(c)).collect()
+        };
+        assert_eq!(cells(grid.ray((0, 1), Direction::Right).collect()), "ef");
+        assert_eq!(cells(grid.ray((2, 2), Direction::UpLeft).collect()), "ea");
+        assert_eq!(grid.ray((0, 0), Direction::Up).count(), 0);
+        assert_eq!(cells(grid.row(1).collect()), "def");
+        assert_eq!(cells(grid.column(2).rev().collect()), "ifc");
+        assert_eq!(grid.row(1).nth(2).unwrap().0, (2, 1));
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.row(3).count(), 0);
+    }
+
+    #[test]
+    fn render() {
+        let input = lines!("abc" "def");
+        let grid = Grid::parse(input).unwrap();
+        assert_eq!(grid.to_string(), input.trim_end());
+        let mut set = Grid::new(2, 2, false);
+        set.set(1, 0, true);
+        assert_eq!(set.to_string(), ".#\n..");
+        let render = || {
+            grid.render(|&c| char::from(c).to_ascii_uppercase())
+                .overlay(Overlay::new([(0, 0), (1, 1)]).marker('*'))
+                .overlay(Overlay::new([(1, 1), (2, 1)]).color(Color::Red))
+        };
+        assert_eq!(render().to_string(), "*BC\nD*F");
+        assert_eq!(
+            render().colors(true).to_string(),
+            "*BC\nD\x1b[31m*\x1b[0m\x1b[31mF\x1b[0m"
+        );
     }
 }
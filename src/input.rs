@@ -0,0 +1,165 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Where a day's input should be looked for, as specified on the command line.
+pub struct InputOptions {
+    pub path: Option<PathBuf>,
+    pub text: Option<String>,
+    pub directory: PathBuf,
+}
+
+pub enum Source {
+    File(PathBuf),
+    Text(String),
+    Stdin,
+}
+
+impl InputOptions {
+    /// Paths tried in order when no input was given explicitly.
+    pub fn default_paths(&self, day: usize) -> [PathBuf; 2] {
+        [
+            Path::new("inputs").join(format!("day{day:02}.txt")),
+            self.directory.join(format!("day{day}")).join("input"),
+        ]
+    }
+
+    /// First default path containing a file, if any.
+    pub fn find_default(&self, day: usize) -> Option<PathBuf> {
+        self.default_paths(day)
+            .into_iter()
+            .find(|path| path.is_file())
+    }
+
+    /// Resolves the input for a day, in order: `--input`, `--input-text`,
+    /// the default paths and finally stdin when `use_stdin` is set, which
+    /// callers decide on since only they know whether stdin was piped.
+    pub fn resolve(&self, day: usize, use_stdin: bool) -> Result<Source, String> {
+        if let Some(path) = &self.path {
+            return Ok(Source::File(path.clone()));
+        }
+        if let Some(text) = &self.text {
+            return Ok(Source::Text(text.clone()));
+        }
+        let paths = self.default_paths(day);
+        if let Some(path) = paths.iter().find(|path| path.is_file()) {
+            return Ok(Source::File(path.clone()));
+        }
+        if use_stdin {
+            return Ok(Source::Stdin);
+        }
+        let tried: Vec<_> = paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        Err(format!(
+            "No input found for day {day}, tried {}",
+            tried.join(", ")
+        ))
+    }
+}
+
+/// Normalizes line endings to `\n`, removes trailing whitespace from every
+/// line along with trailing blank lines, and ends non-empty input with a
+/// single newline, so that parsers only have to handle one layout.
+pub fn normalize(input: &str) -> String {
+    let mut output = String::with_capacity(input.len() + 1);
+    for line in input.lines() {
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output.truncate(output.trim_end_matches('\n').len());
+    if !output.is_empty() {
+        output.push('\n');
+    }
+    output
+}
+
+impl Source {
+    pub fn read(&self) -> Result<String, String> {
+        match self {
+            Self::File(path) => fs::read_to_string(path)
+                .map_err(|e| format!("Unable to read {}: {e}", path.display())),
+            Self::Text(text) => Ok(text.clone()),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("Unable to read stdin: {e}"))?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Opens the input to be read incrementally, as is without normalizing it.
+    pub fn open(&self) -> Result<Box<dyn BufRead + '_>, String> {
+        match self {
+            Self::File(path) => File::open(path)
+                .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+                .map_err(|e| format!("Unable to read {}: {e}", path.display())),
+            Self::Text(text) => Ok(Box::new(text.as_bytes())),
+            Self::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{normalize, InputOptions, Source};
+    use crate::lines;
+    use std::path::{Path, PathBuf};
+
+    fn options(path: Option<&str>, text: Option<&str>) -> InputOptions {
+        InputOptions {
+            path: path.map(PathBuf::from),
+            text: text.map(String::from),
+            directory: PathBuf::from("src"),
+        }
+    }
+
+    #[test]
+    fn normalize_layouts() {
+        let expected = lines!("    [D]" "[N] [C]" "" " 1   2");
+        assert_eq!(normalize(expected), expected);
+        assert_eq!(
+            normalize("    [D]    \r\n[N] [C]\r\n   \r\n 1   2 \r\n\r\n"),
+            expected
+        );
+        assert_eq!(normalize(expected.trim_end()), expected);
+        assert_eq!(normalize(""), "");
+        assert_eq!(normalize("\n \n"), "");
+    }
+
+    #[test]
+    fn explicit_path_wins() {
+        let source = options(Some("custom"), Some("text")).resolve(1, false);
+        assert!(matches!(source, Ok(Source::File(path)) if path == Path::new("custom")));
+    }
+
+    #[test]
+    fn text_before_defaults() {
+        let source = options(None, Some("text")).resolve(1, false);
+        assert!(matches!(source, Ok(Source::Text(text)) if text == "text"));
+    }
+
+    #[test]
+    fn default_paths() {
+        let source = options(None, None).resolve(4, false);
+        assert!(matches!(source, Ok(Source::File(path)) if path == Path::new("src/day4/input")));
+    }
+
+    #[test]
+    fn missing_input_names_paths() {
+        let mut options = options(None, None);
+        options.directory = PathBuf::from("nonexistent");
+        let error = options.resolve(7, false).err().unwrap();
+        assert_eq!(
+            error,
+            format!(
+                "No input found for day 7, tried {}, {}",
+                Path::new("inputs/day07.txt").display(),
+                Path::new("nonexistent/day7/input").display(),
+            )
+        );
+    }
+}
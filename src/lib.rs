@@ -0,0 +1,55 @@
+//! Solutions to Advent of Code 2022 along with the types they are built on.
+//!
+//! Every day implements [`day::Day`], and [`SOLUTIONS`] lists them all behind
+//! the object safe [`day::Solution`] interface used by the command line tool.
+
+#![recursion_limit = "256"]
+
+pub mod answer;
+pub mod answers;
+pub mod day;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod grid;
+pub mod input;
+pub mod params;
+pub mod parse;
+pub mod report;
+pub mod rng;
+pub mod search;
+mod testmacros;
+pub mod timing;
+
+use day::Solution;
+
+/// Registry of all implemented days, ordered by day number.
+pub const SOLUTIONS: &[&dyn Solution] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+];
@@ -1,92 +1,315 @@
-#![recursion_limit = "256"]
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod grid;
-#[cfg(test)]
-mod testmacros;
+mod error;
+mod options;
+mod parallel;
+mod repl;
+mod selection;
+mod watch;
 
+use advent_of_code_2022::answers::Answers;
+use advent_of_code_2022::day::Solution;
+use advent_of_code_2022::input::Source;
+use advent_of_code_2022::params::Params;
+use advent_of_code_2022::report::{DayReport, DayRun, Format, PartReport};
+use advent_of_code_2022::rng::Rng;
+use advent_of_code_2022::SOLUTIONS;
+use error::CliError;
+use options::{Options, USAGE};
 use std::env;
-use std::error::Error;
-use std::io::{self, Read, Write};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-struct Solution {
-    part1: fn(&str) -> Result<String, Box<dyn Error + '_>>,
-    part2: fn(&str) -> Result<String, Box<dyn Error + '_>>,
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            e.exit_code()
+        }
+    }
 }
 
-const SOLUTIONS: &[Solution] = &[
-    day1::DAY1,
-    day2::DAY2,
-    day3::DAY3,
-    day4::DAY4,
-    day5::DAY5,
-    day6::DAY6,
-    day7::DAY7,
-    day8::DAY8,
-    day9::DAY9,
-    day10::DAY10,
-    day11::DAY11,
-    day12::DAY12,
-    day13::DAY13,
-    day14::DAY14,
-    day15::DAY15,
-];
-
-const USAGE: &str = "advent-of-code-2022
-USAGE:
-    advent-of-code-2022 <day> [input]
-FLAGS:
-    -h, --help      Prints help information
-ARGS:
-    <day>      Day for which a solution should be ran
-    <input>    Input, if not provided taken from stdin";
-
-fn main() -> Result<(), Box<dyn Error>> {
+fn try_main() -> Result<(), CliError> {
     let mut args = env::args();
     // Skip the program name
     args.next();
-    let mut day = args.next();
-    if [Some("--help"), Some("-h")].contains(&day.as_deref()) {
-        day = None;
-    }
-    let day = if let Some(day) = day {
-        day
+    let options = if let Some(options) = Options::parse(args).map_err(CliError::Usage)? {
+        options
     } else {
         eprintln!("{}", USAGE);
         return Ok(());
     };
-    let day: usize = day.parse()?;
-    let solution = SOLUTIONS.get(day - 1).ok_or("Day number out of range")?;
-    let input = if let Some(input) = args.next() {
-        input
+    match options.days.as_str() {
+        "repl" => return Ok(repl::repl(SOLUTIONS, io::stdin().lock(), io::stdout())?),
+        "list" => return Ok(list(&options)?),
+        "generate" => return generate(&options),
+        _ => {}
+    }
+    let answers = if let Some(path) = &options.answers {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| CliError::Input(format!("Unable to read {}: {e}", path.display())))?;
+        let answers = Answers::parse(&contents)
+            .map_err(|e| CliError::Input(format!("Invalid {}: {e}", path.display())))?;
+        Some(answers)
     } else {
-        let mut input = String::new();
-        io::stdin().read_to_string(&mut input)?;
-        input
+        None
     };
-    writeln!(
-        io::stdout(),
-        "Part 1: {}",
-        (solution.part1)(&input).map_err(|e| e.to_string())?
-    )?;
-    writeln!(
-        io::stdout(),
-        "Part 2: {}",
-        (solution.part2)(&input).map_err(|e| e.to_string())?
+    let days = selection::parse_days(&options.days, SOLUTIONS.len()).map_err(CliError::Usage)?;
+    if days.len() > 1 && (options.input.path.is_some() || options.input.text.is_some()) {
+        return Err(CliError::Usage(
+            "--input and --input-text can only be used with a single day".into(),
+        ));
+    }
+    let declared: Vec<_> = days
+        .iter()
+        .map(|&day| SOLUTIONS[day - 1].params())
+        .collect();
+    let params = Params::for_days(&declared, &options.params).map_err(CliError::Usage)?;
+    let days: Vec<_> = days.into_iter().zip(params).collect();
+    if options.watch {
+        let &[(day, ref params)] = days.as_slice() else {
+            return Err(CliError::Usage(
+                "--watch can only be used with a single day".into(),
+            ));
+        };
+        let Source::File(path) = options.input.resolve(day, false).map_err(CliError::Input)? else {
+            return Err(CliError::Usage(
+                "--watch requires the input to be a file".into(),
+            ));
+        };
+        return Ok(watch::watch(&path, options.format, |input| DayReport {
+            day,
+            outcome: Ok(run(
+                day,
+                SOLUTIONS[day - 1],
+                input,
+                params,
+                &options,
+                answers.as_ref(),
+            )),
+        })?);
+    }
+    if options.stream {
+        let &[(day, ref params)] = days.as_slice() else {
+            return Err(CliError::Usage(
+                "--stream can only be used with a single day".into(),
+            ));
+        };
+        return stream(day, params, &options, answers.as_ref());
+    }
+    run_days(&days, &options, answers.as_ref())
+}
+
+/// Selected parts, both unless --part was given.
+fn parts(options: &Options) -> Vec<u8> {
+    match options.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+fn verify(day: usize, run: &mut DayRun, answers: Option<&Answers>) {
+    if let Some(answers) = answers {
+        for part in &mut run.parts {
+            if let Ok((answer, _)) = &part.outcome {
+                part.verdict = Some(answers.verify(day, part.part, answer));
+            }
+        }
+    }
+}
+
+/// Parses the input and runs the selected parts of a solution, each
+/// independently of the other.
+fn run(
+    day: usize,
+    solution: &dyn Solution,
+    input: &str,
+    params: &Params,
+    options: &Options,
+    answers: Option<&Answers>,
+) -> DayRun {
+    let parts = parts(options);
+    let mut run = catch_panic(&parts, || {
+        solution.run(input, &parts, options.bench, params)
+    });
+    verify(day, &mut run, answers);
+    run
+}
+
+/// Runs `run`, turning a panic into a failure of every part so that the
+/// remaining days still run and get summarized.
+fn catch_panic(parts: &[u8], run: impl FnOnce() -> DayRun) -> DayRun {
+    panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("unknown cause");
+        DayRun {
+            parse: Ok(None),
+            parts: parts
+                .iter()
+                .map(|&part| PartReport {
+                    part,
+                    outcome: Err(format!("Panicked: {message}")),
+                    verdict: None,
+                })
+                .collect(),
+        }
+    })
+}
+
+/// Runs a single day on its input read line by line, which is opened again
+/// for every part and run.
+fn stream(
+    day: usize,
+    params: &Params,
+    options: &Options,
+    answers: Option<&Answers>,
+) -> Result<(), CliError> {
+    let source = options
+        .input
+        .resolve(day, !io::stdin().is_terminal())
+        .map_err(CliError::Input)?;
+    let parts = parts(options);
+    if matches!(source, Source::Stdin) && parts.len() * options.bench > 1 {
+        return Err(CliError::Usage(
+            "--stream can only read stdin once, select a part with --part and don't use --bench"
+                .into(),
+        ));
+    }
+    let Some(mut run) = SOLUTIONS[day - 1].stream(&|| source.open(), &parts, options.bench, params)
+    else {
+        return Err(CliError::Usage(format!(
+            "Day {day} doesn't support --stream"
+        )));
+    };
+    verify(day, &mut run, answers);
+    let report = DayReport {
+        day,
+        outcome: Ok(run),
+    };
+    report.write(options.format, false, &mut io::stdout(), &mut io::stderr())?;
+    let failures = report.failures();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Failed(failures))
+    }
+}
+
+/// Runs the given days with their parameters, printing reports in order and
+/// a summary when there is more than one day.
+fn run_days(
+    days: &[(usize, Params)],
+    options: &Options,
+    answers: Option<&Answers>,
+) -> Result<(), CliError> {
+    let single_day = days.len() == 1;
+    let mut failures = Vec::new();
+    let mut summed = Duration::ZERO;
+    let start = Instant::now();
+    parallel::ordered_map(
+        days,
+        options.jobs,
+        |&(day, ref params)| DayReport {
+            day,
+            outcome: options
+                .input
+                .resolve(day, single_day && !io::stdin().is_terminal())
+                .and_then(|source| source.read())
+                .map(|input| run(day, SOLUTIONS[day - 1], &input, params, options, answers)),
+        },
+        |report| {
+            report.write(
+                options.format,
+                !single_day,
+                &mut io::stdout(),
+                &mut io::stderr(),
+            )?;
+            summed += report.elapsed();
+            failures.extend(report.failures());
+            io::Result::Ok(())
+        },
     )?;
+    if !single_day && options.format == Format::Text {
+        writeln!(
+            io::stdout(),
+            "Total time: {:?} (summed time: {summed:?})",
+            start.elapsed()
+        )?;
+        if failures.is_empty() {
+            writeln!(io::stdout(), "All {} days succeeded", days.len())?;
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::Failed(failures))
+    }
+}
+
+/// Prints a random input for a single day.
+fn generate(options: &Options) -> Result<(), CliError> {
+    let days = options
+        .generate
+        .as_deref()
+        .ok_or_else(|| CliError::Usage("Expected a day after generate".into()))?;
+    let &[day] = selection::parse_days(days, SOLUTIONS.len())
+        .map_err(CliError::Usage)?
+        .as_slice()
+    else {
+        return Err(CliError::Usage(
+            "generate can only be used with a single day".into(),
+        ));
+    };
+    let input = SOLUTIONS[day - 1].generate(&mut Rng::new(options.seed), options.size);
+    io::stdout().write_all(input.as_bytes())?;
+    Ok(())
+}
+
+/// Lists registered days along with the input that would be used for them
+/// and their parameters.
+fn list(options: &Options) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    for day in SOLUTIONS {
+        let input = options.input.find_default(day.number()).map_or_else(
+            || "no input found".into(),
+            |path| path.display().to_string(),
+        );
+        writeln!(stdout, "{:>2}  {:<25} {input}", day.number(), day.title())?;
+        for param in day.params() {
+            writeln!(
+                stdout,
+                "      {:<25} {} [default: {}]",
+                param.name, param.description, param.default
+            )?;
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::catch_panic;
+    use advent_of_code_2022::report::DayRun;
+
+    #[test]
+    fn panic_fails_parts() {
+        let run = catch_panic(&[1, 2], || panic!("Index out of bounds"));
+        assert_eq!(run.parts.len(), 2);
+        for part in &run.parts {
+            assert_eq!(
+                part.outcome.as_ref().err().unwrap(),
+                "Panicked: Index out of bounds"
+            );
+        }
+        let run = catch_panic(&[1], || DayRun {
+            parse: Ok(None),
+            parts: Vec::new(),
+        });
+        assert!(run.parts.is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+use advent_of_code_2022::input::InputOptions;
+use advent_of_code_2022::report::Format;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+
+pub const USAGE: &str = "advent-of-code-2022
+USAGE:
+    advent-of-code-2022 [OPTIONS] <days>
+    advent-of-code-2022 [OPTIONS] list
+    advent-of-code-2022 generate <day> [--seed <seed>] [--size <size>]
+    advent-of-code-2022 repl
+FLAGS:
+    -h, --help      Prints help information
+OPTIONS:
+    --input <path>          File containing the input
+    --input-text <text>     Input given directly as text
+    --inputs <directory>    Directory containing dayN/input files [default: src]
+    --bench <runs>          Run each part the given number of times and report timings
+    --format <format>       Output format, either text or json [default: text]
+    --verify                Compare answers against the answers file
+    --answers <file>        Answers file used by --verify [default: answers.toml]
+    --part <part>           Only run the given part, either 1 or 2
+    --watch                 Rerun a single day whenever its input file changes
+    --stream                Read the input of a single day line by line instead of
+                            loading it in memory, for days 1, 6 and 9
+    --jobs <jobs>           Number of days to run in parallel, 0 for one per CPU [default: 1]
+    --param <name=value>    Override a puzzle parameter, such as row=10 for the day 15
+                            example; `list` shows the available parameters
+    --seed <seed>           Seed of the input printed by `generate` [default: 0]
+    --size <size>           Rough number of lines, groups or other entries in the input
+                            printed by `generate` [default: 100]
+ARGS:
+    <days>     Days for which solutions should be ran: a day number, a range
+               such as 1-5, a list such as 3,7,12, `latest` or `all`
+INPUT:
+    Unless --input or --input-text is given, inputs/dayNN.txt and then
+    <inputs>/dayN/input are tried, falling back to stdin when it is piped.
+EXIT STATUS:
+    0    All selected parts succeeded
+    1    Unable to write output
+    2    Invalid command line arguments
+    3    Missing or unreadable input
+    4    A solution returned an error
+    5    An answer didn't match the answers file";
+
+pub struct Options {
+    pub days: String,
+    pub input: InputOptions,
+    pub bench: usize,
+    pub format: Format,
+    pub answers: Option<PathBuf>,
+    pub part: Option<u8>,
+    pub jobs: usize,
+    pub watch: bool,
+    pub stream: bool,
+    /// Day given to `generate`.
+    pub generate: Option<String>,
+    pub seed: u64,
+    pub size: usize,
+    /// Parameter overrides as `(name, value)` pairs, in the order given.
+    pub params: Vec<(String, String)>,
+}
+
+impl Options {
+    /// Parses command line arguments, returning `None` when help was requested.
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut days = None;
+        let mut input = InputOptions {
+            path: None,
+            text: None,
+            directory: PathBuf::from("src"),
+        };
+        let mut bench = 1;
+        let mut format = Format::Text;
+        let mut verify = false;
+        let mut answers = PathBuf::from("answers.toml");
+        let mut part = None;
+        let mut jobs = 1;
+        let mut watch = false;
+        let mut stream = false;
+        let mut generate = None;
+        let mut seed = 0;
+        let mut size = 100;
+        let mut params = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--input" => input.path = Some(value(&mut args, &arg)?),
+                "--input-text" => input.text = Some(value(&mut args, &arg)?),
+                "--inputs" => input.directory = value(&mut args, &arg)?,
+                "--bench" => {
+                    bench = value(&mut args, &arg)?;
+                    if bench == 0 {
+                        return Err("Number of benchmark runs must be positive".into());
+                    }
+                }
+                "--format" => format = value(&mut args, &arg)?,
+                "--verify" => verify = true,
+                "--answers" => answers = value(&mut args, &arg)?,
+                "--part" => match value::<u8>(&mut args, &arg)? {
+                    part_number @ (1 | 2) => part = Some(part_number),
+                    _ => return Err("Expected 1 or 2 after --part".into()),
+                },
+                "--jobs" => {
+                    jobs = value(&mut args, &arg)?;
+                    if jobs == 0 {
+                        jobs = thread::available_parallelism().map_or(1, |jobs| jobs.get());
+                    }
+                }
+                "--watch" => watch = true,
+                "--stream" => stream = true,
+                "--seed" => seed = value(&mut args, &arg)?,
+                "--size" => {
+                    size = value(&mut args, &arg)?;
+                    if size == 0 {
+                        return Err("Size of generated inputs must be positive".into());
+                    }
+                }
+                "--param" => {
+                    let param: String = value(&mut args, &arg)?;
+                    let (name, value) = param
+                        .split_once('=')
+                        .ok_or("Expected name=value after --param")?;
+                    params.push((name.into(), value.into()));
+                }
+                _ if days.is_none() => days = Some(arg),
+                _ if days.as_deref() == Some("generate") && generate.is_none() => {
+                    generate = Some(arg)
+                }
+                _ => return Err(format!("Unexpected argument {arg}")),
+            }
+        }
+        if input.path.is_some() && input.text.is_some() {
+            return Err("--input and --input-text can't be used together".into());
+        }
+        Ok(days.map(|days| Self {
+            days,
+            input,
+            bench,
+            format,
+            answers: verify.then_some(answers),
+            part,
+            jobs,
+            watch,
+            stream,
+            generate,
+            seed,
+            size,
+            params,
+        }))
+    }
+}
+
+fn value<T>(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    args.next()
+        .ok_or_else(|| format!("Expected a value after {flag}"))?
+        .parse()
+        .map_err(|e| format!("Invalid value for {flag}: {e}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Options;
+    use advent_of_code_2022::report::Format;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn defaults() {
+        let options = parse(&["12"]).unwrap().unwrap();
+        assert_eq!(options.days, "12");
+        assert_eq!(options.bench, 1);
+        assert!(options.format == Format::Text);
+        assert!(options.answers.is_none());
+        assert_eq!(options.part, None);
+    }
+
+    #[test]
+    fn flags() {
+        let options = parse(&["--part", "2", "all", "--verify", "--format", "json"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.days, "all");
+        assert_eq!(options.part, Some(2));
+        assert!(options.format == Format::Json);
+        assert!(options.answers.is_some());
+        assert!(!options.stream);
+        assert!(parse(&["9", "--stream"]).unwrap().unwrap().stream);
+    }
+
+    #[test]
+    fn params() {
+        let options = parse(&["15", "--param", "row=10", "--param", "search_space=20"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            options.params,
+            [
+                ("row".to_string(), "10".to_string()),
+                ("search_space".to_string(), "20".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse(&["15", "--param", "row"]).err().unwrap(),
+            "Expected name=value after --param"
+        );
+    }
+
+    #[test]
+    fn generate() {
+        let options = parse(&["generate", "5", "--seed", "42"]).unwrap().unwrap();
+        assert_eq!(options.days, "generate");
+        assert_eq!(options.generate.as_deref(), Some("5"));
+        assert_eq!((options.seed, options.size), (42, 100));
+        assert_eq!(
+            parse(&["generate", "5", "--size", "0"]).err().unwrap(),
+            "Size of generated inputs must be positive"
+        );
+        assert_eq!(
+            parse(&["generate", "5", "6"]).err().unwrap(),
+            "Unexpected argument 6"
+        );
+    }
+
+    #[test]
+    fn help() {
+        assert!(parse(&[]).unwrap().is_none());
+        assert!(parse(&["1", "--help"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            parse(&["1", "--part", "3"]).err().unwrap(),
+            "Expected 1 or 2 after --part"
+        );
+        assert_eq!(
+            parse(&["1", "--bench"]).err().unwrap(),
+            "Expected a value after --bench"
+        );
+        assert_eq!(parse(&["1", "2"]).err().unwrap(), "Unexpected argument 2");
+        assert_eq!(
+            parse(&["1", "--input", "a.txt", "--input-text", "1"])
+                .err()
+                .unwrap(),
+            "--input and --input-text can't be used together"
+        );
+        assert!(parse(&["1", "--bench", "x"]).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Maps `items` with `f` on `jobs` threads, passing the results to `consume`
+/// in the original order as soon as all earlier results are available.
+///
+/// Stops early if `consume` returns an error.
+pub fn ordered_map<T, R, E>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+    mut consume: impl FnMut(R) -> Result<(), E>,
+) -> Result<(), E>
+where
+    T: Sync,
+    R: Send,
+{
+    if jobs <= 1 {
+        return items.iter().try_for_each(|item| consume(f(item)));
+    }
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, f(item))).is_err() {
+                        // The consumer failed, no point in continuing
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_index) {
+                consume(result)?;
+                next_index += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::ordered_map;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn preserves_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let mut output = Vec::new();
+        ordered_map(
+            &items,
+            4,
+            |&item| {
+                // Make earlier items finish later
+                thread::sleep(Duration::from_millis(20 - item));
+                item * 2
+            },
+            |result| {
+                output.push(result);
+                Ok::<_, ()>(())
+            },
+        )
+        .unwrap();
+        assert_eq!(output, (0..20).map(|item| item * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stops_on_error() {
+        let items: Vec<u32> = (0..100).collect();
+        let mut consumed = 0;
+        let result = ordered_map(
+            &items,
+            3,
+            |&item| item,
+            |item| {
+                consumed += 1;
+                if item == 5 {
+                    Err(item)
+                } else {
+                    Ok(())
+                }
+            },
+        );
+        assert_eq!(result, Err(5));
+        assert_eq!(consumed, 6);
+    }
+}
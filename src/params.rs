@@ -0,0 +1,175 @@
+/// Puzzle constant which can be overridden with `--param name=value`, for
+/// instance to run a solution on the smaller example from the puzzle text.
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    pub description: &'static str,
+}
+
+/// Parameter values given for a day, anything not given uses its default.
+#[derive(Default)]
+pub struct Params {
+    overrides: Vec<(&'static str, i64)>,
+}
+
+impl Params {
+    /// Checks `name=value` overrides against the parameters a day declares.
+    pub fn new(declared: &[Param], overrides: &[(String, String)]) -> Result<Self, String> {
+        let overrides = overrides
+            .iter()
+            .map(|(name, value)| {
+                let param = declared
+                    .iter()
+                    .find(|param| param.name == name)
+                    .ok_or_else(|| match declared {
+                        [] => format!("Unknown parameter {name}, this day has no parameters"),
+                        _ => format!(
+                            "Unknown parameter {name}, expected one of {}",
+                            declared
+                                .iter()
+                                .map(|param| param.name)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    })?;
+                let value = value
+                    .parse()
+                    .map_err(|e| format!("Invalid value for parameter {name}: {e}"))?;
+                Ok((param.name, value))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { overrides })
+    }
+
+    /// Parameter values for each of the selected days, applying every
+    /// override only to the days declaring it. With a single day this is the
+    /// same as [`Params::new`], otherwise an override is only rejected when
+    /// none of the days declare it.
+    pub fn for_days(
+        days: &[&[Param]],
+        overrides: &[(String, String)],
+    ) -> Result<Vec<Self>, String> {
+        if let [declared] = days {
+            return Ok(vec![Self::new(declared, overrides)?]);
+        }
+        let declares = |declared: &[Param], name: &str| declared.iter().any(|p| p.name == name);
+        if let Some((name, _)) = overrides
+            .iter()
+            .find(|(name, _)| !days.iter().any(|declared| declares(declared, name)))
+        {
+            return Err(format!(
+                "Unknown parameter {name}, none of the selected days have it"
+            ));
+        }
+        days.iter()
+            .map(|declared| {
+                let overrides: Vec<_> = overrides
+                    .iter()
+                    .filter(|(name, _)| declares(declared, name))
+                    .cloned()
+                    .collect();
+                Self::new(declared, &overrides)
+            })
+            .collect()
+    }
+
+    /// Value of `param`, converted to the type the solution works with.
+    pub fn get<T: TryFrom<i64>>(&self, param: &Param) -> Result<T, String> {
+        let value = self
+            .overrides
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == param.name)
+            .map_or(param.default, |&(_, value)| value);
+        T::try_from(value).map_err(|_| format!("Parameter {} out of range: {value}", param.name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Param, Params};
+
+    const ROW: Param = Param {
+        name: "row",
+        default: 2_000_000,
+        description: "Row to inspect",
+    };
+    const ROUNDS: Param = Param {
+        name: "rounds",
+        default: 20,
+        description: "Rounds to simulate",
+    };
+
+    fn overrides(overrides: &[(&str, &str)]) -> Vec<(String, String)> {
+        overrides
+            .iter()
+            .map(|&(name, value)| (name.into(), value.into()))
+            .collect()
+    }
+
+    #[test]
+    fn defaults() {
+        let params = Params::default();
+        assert_eq!(params.get::<i32>(&ROW), Ok(2_000_000));
+        assert_eq!(params.get::<u8>(&ROUNDS), Ok(20));
+    }
+
+    #[test]
+    fn overridden() {
+        let params =
+            Params::new(&[ROW, ROUNDS], &overrides(&[("row", "10"), ("row", "-3")])).unwrap();
+        assert_eq!(params.get::<i32>(&ROW), Ok(-3));
+        assert_eq!(params.get::<u32>(&ROUNDS), Ok(20));
+        assert_eq!(
+            params.get::<u32>(&ROW).err().unwrap(),
+            "Parameter row out of range: -3"
+        );
+    }
+
+    #[test]
+    fn several_days() {
+        let days: [&[Param]; 3] = [&[], &[ROW], &[ROW, ROUNDS]];
+        let params = Params::for_days(&days, &overrides(&[("rounds", "5")])).unwrap();
+        assert_eq!(params.len(), 3);
+        assert_eq!(params[1].get::<i32>(&ROW), Ok(2_000_000));
+        assert_eq!(params[2].get::<u8>(&ROUNDS), Ok(5));
+        assert_eq!(
+            Params::for_days(&days, &overrides(&[("column", "1")]))
+                .err()
+                .unwrap(),
+            "Unknown parameter column, none of the selected days have it"
+        );
+        assert_eq!(
+            Params::for_days(&days, &overrides(&[("row", "x")]))
+                .err()
+                .unwrap(),
+            "Invalid value for parameter row: invalid digit found in string"
+        );
+        assert_eq!(
+            Params::for_days(&[&[]], &overrides(&[("row", "1")]))
+                .err()
+                .unwrap(),
+            "Unknown parameter row, this day has no parameters"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            Params::new(&[ROW, ROUNDS], &overrides(&[("column", "1")]))
+                .err()
+                .unwrap(),
+            "Unknown parameter column, expected one of row, rounds"
+        );
+        assert_eq!(
+            Params::new(&[], &overrides(&[("row", "1")])).err().unwrap(),
+            "Unknown parameter row, this day has no parameters"
+        );
+        assert_eq!(
+            Params::new(&[ROW], &overrides(&[("row", "x")]))
+                .err()
+                .unwrap(),
+            "Invalid value for parameter row: invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Errors pointing at the part of the puzzle input a parser rejected.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::BufRead;
+
+/// Error about `span`, which must be a slice of the puzzle input so that
+/// [`ParseError::locate`] can find where it is.
+#[derive(Debug)]
+pub struct ParseError<'a> {
+    pub span: &'a str,
+    pub message: String,
+}
+
+impl<'a> ParseError<'a> {
+    pub fn new(span: &'a str, message: impl Display) -> Self {
+        Self {
+            span,
+            message: message.to_string(),
+        }
+    }
+
+    /// Finds the line and column of the span in `input`. A span not sliced
+    /// from `input` is reported at its end.
+    pub fn locate(&self, input: &str) -> InputError {
+        let start = (self.span.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+        let start = if start <= input.len() && input.is_char_boundary(start) {
+            start
+        } else {
+            input.len()
+        };
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let text = input[line_start..line_end].trim_end_matches('\r');
+        let width = self.span.chars().take_while(|&c| c != '\n').count();
+        InputError {
+            message: self.message.clone(),
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..start].chars().count() + 1,
+            width: width.max(1),
+            text: text.into(),
+        }
+    }
+}
+
+impl Display for ParseError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ParseError<'_> {}
+
+/// Attaches the part of the input an error is about.
+pub trait At<'a, T> {
+    fn at(self, span: &'a str) -> Result<T, ParseError<'a>>;
+}
+
+impl<'a, T, E: Display> At<'a, T> for Result<T, E> {
+    fn at(self, span: &'a str) -> Result<T, ParseError<'a>> {
+        self.map_err(|e| ParseError::new(span, e))
+    }
+}
+
+/// Parse error located in the input, with 1-based line and column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    /// Number of characters the error is about.
+    pub width: usize,
+    /// The whole line on which the error is.
+    pub text: String,
+}
+
+impl InputError {
+    /// Formats the error followed by the offending line, with carets below
+    /// the rejected text.
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        format!(
+            "{self}:\n{number} | {}\n{:width$} | {:column$}{}",
+            self.text,
+            "",
+            "",
+            "^".repeat(self.width),
+            width = number.len(),
+            column = self.column - 1,
+        )
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl Error for InputError {}
+
+/// Calls `f` with every line of `input` as [`crate::input::normalize`] would
+/// leave it, reusing a single buffer so that memory stays bounded by the
+/// longest line. Parse errors are located on their line and returned as
+/// [`InputError`], along with any I/O error.
+pub fn for_each_line(
+    input: &mut dyn BufRead,
+    mut f: impl FnMut(&str) -> Result<(), ParseError<'_>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut buffer = String::new();
+    let mut number = 0;
+    // Blank lines are only passed on once a line follows them, as trailing
+    // ones are dropped.
+    let mut blank_lines = 0;
+    loop {
+        buffer.clear();
+        if input.read_line(&mut buffer)? == 0 {
+            return Ok(());
+        }
+        number += 1;
+        let line = buffer.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        for blank_line in number - blank_lines..number {
+            f("").map_err(|e| InputError {
+                line: blank_line,
+                ..e.locate("")
+            })?;
+        }
+        blank_lines = 0;
+        f(line).map_err(|e| InputError {
+            line: number,
+            ..e.locate(line)
+        })?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{for_each_line, At, InputError, ParseError};
+    use crate::lines;
+
+    #[test]
+    fn locate() {
+        let input = lines!("1-2,3-4" "5-6,x-8");
+        let span = &input[12..13];
+        let error = "x".parse::<u8>().at(span).unwrap_err().locate(input);
+        assert_eq!((error.line, error.column, error.width), (2, 5, 1));
+        assert_eq!(error.text, "5-6,x-8");
+        assert_eq!(
+            error.render(),
+            lines!(
+                "invalid digit found in string at line 2, column 5:"
+                "2 | 5-6,x-8"
+                "  |     ^"
+            )
+            .trim_end()
+        );
+    }
+
+    #[test]
+    fn multi_line_span() {
+        let input = lines!("ab" "cd");
+        let error = ParseError::new(&input[1..], "Unexpected").locate(input);
+        assert_eq!((error.line, error.column, error.width), (1, 2, 1));
+    }
+
+    #[test]
+    fn end_of_input() {
+        let input = lines!("ab");
+        let error = ParseError::new(&input[input.len()..], "Expected more").locate(input);
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.text, "");
+        let error = ParseError::new("elsewhere", "Expected more").locate(input);
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn lines_of_stream() {
+        let mut lines = Vec::new();
+        let mut input = "1\r\n2 \n\n  \n3\n\n".as_bytes();
+        for_each_line(&mut input, |line| {
+            lines.push(line.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(lines, ["1", "2", "", "", "3"]);
+
+        let mut input = "R 4\nU x\n".as_bytes();
+        let error = for_each_line(&mut input, |line| {
+            let (_, count) = line.split_once(' ').unwrap();
+            count.parse::<u8>().at(count)?;
+            Ok(())
+        })
+        .unwrap_err()
+        .downcast::<InputError>()
+        .unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "U x");
+    }
+}
@@ -0,0 +1,196 @@
+use advent_of_code_2022::day::Solution;
+use advent_of_code_2022::params::Params;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+const SENTINEL: &str = "EOF";
+
+const HELP: &str = "Commands:
+    day <day>      Select a day
+    paste          Read input from the following lines, up to a line containing only EOF
+    load <path>    Read input from a file
+    param <n=v>    Override a parameter of the selected day
+    run [part]     Run both parts, or only the given one, on the current input
+    help           Show this message
+    quit           Exit";
+
+/// Interactive loop reading commands from `input` and writing results to `output`.
+pub fn repl(
+    solutions: &[&dyn Solution],
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut day = None;
+    let mut puzzle_input = None;
+    let mut overrides = Vec::new();
+    let mut params = Params::default();
+    writeln!(output, "{HELP}")?;
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let (command, argument) = line
+            .trim()
+            .split_once(' ')
+            .map_or((line.trim(), ""), |(command, argument)| {
+                (command, argument.trim())
+            });
+        match command {
+            "" => {}
+            "day" => match argument.parse() {
+                Ok(number @ 1..) if number <= solutions.len() => {
+                    day = Some(number);
+                    overrides.clear();
+                    params = Params::default();
+                    writeln!(
+                        output,
+                        "Selected day {number}: {}",
+                        solutions[number - 1].title()
+                    )?;
+                }
+                _ => writeln!(output, "Expected a day between 1 and {}", solutions.len())?,
+            },
+            "paste" => {
+                let mut text = String::new();
+                loop {
+                    let mut line = String::new();
+                    if input.read_line(&mut line)? == 0 || line.trim_end() == SENTINEL {
+                        break;
+                    }
+                    text.push_str(&line);
+                }
+                writeln!(output, "Read {} lines", text.lines().count())?;
+                puzzle_input = Some(text);
+            }
+            "load" => match fs::read_to_string(argument) {
+                Ok(text) => {
+                    writeln!(output, "Read {} lines", text.lines().count())?;
+                    puzzle_input = Some(text);
+                }
+                Err(e) => writeln!(output, "Unable to read {argument}: {e}")?,
+            },
+            "param" => {
+                let Some(day) = day else {
+                    writeln!(output, "Select a day first")?;
+                    continue;
+                };
+                let Some((name, value)) = argument.split_once('=') else {
+                    writeln!(output, "Expected name=value")?;
+                    continue;
+                };
+                overrides.push((name.to_string(), value.to_string()));
+                match Params::new(solutions[day - 1].params(), &overrides) {
+                    Ok(new_params) => params = new_params,
+                    Err(e) => {
+                        overrides.pop();
+                        writeln!(output, "{e}")?;
+                    }
+                }
+            }
+            "run" => {
+                let parts: &[u8] = match argument {
+                    "" => &[1, 2],
+                    "1" => &[1],
+                    "2" => &[2],
+                    _ => {
+                        writeln!(output, "Expected part 1 or 2")?;
+                        continue;
+                    }
+                };
+                let (Some(day), Some(puzzle_input)) = (day, &puzzle_input) else {
+                    writeln!(output, "Select a day and provide an input first")?;
+                    continue;
+                };
+                let run = solutions[day - 1].run(puzzle_input, parts, 1, &params);
+                match &run.parse {
+                    Ok(Some(timing)) => writeln!(output, "Parse: {timing}")?,
+                    Ok(None) => {}
+                    Err(e) => writeln!(output, "Parsing failed: {}", e.render())?,
+                }
+                for part in &run.parts {
+                    let number = part.part;
+                    match &part.outcome {
+                        Ok((answer, timing)) if answer.is_multiline() => {
+                            writeln!(output, "Part {number} ({timing}):\n{answer}")?;
+                        }
+                        Ok((answer, timing)) => {
+                            writeln!(output, "Part {number}: {answer} ({timing})")?;
+                        }
+                        Err(e) => writeln!(output, "Part {number} failed: {e}")?,
+                    }
+                }
+            }
+            "help" => writeln!(output, "{HELP}")?,
+            "quit" | "exit" => return Ok(()),
+            _ => writeln!(output, "Unrecognized command {command}, try help")?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::repl;
+    use advent_of_code_2022::{lines, SOLUTIONS};
+
+    fn run(commands: &str) -> String {
+        let mut output = Vec::new();
+        repl(SOLUTIONS, commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn paste_and_run() {
+        let output = run(lines!(
+            "day 2"
+            "paste"
+            "A Y"
+            "B X"
+            "C Z"
+            "EOF"
+            "run 1"
+            "day 4"
+            "run"
+        ));
+        assert!(output.contains("Selected day 2"));
+        assert!(output.contains("Read 3 lines"));
+        assert!(output.contains("Part 1: 15 ("));
+        assert!(output.contains(lines!(
+            "Parsing failed: Missing first dash at line 1, column 1:"
+            "1 | A Y"
+            "  | ^^^"
+        )));
+    }
+
+    #[test]
+    fn params() {
+        let output = run(lines!(
+            "day 11"
+            "param rounds=1"
+            "param part1_rounds=1"
+            "load src/day11/input"
+            "run 1"
+            "day 1"
+            "param part1_rounds=1"
+        ));
+        assert!(
+            output.contains("Unknown parameter rounds, expected one of part1_rounds, part2_rounds")
+        );
+        assert!(output.contains("Part 1: 195 ("));
+        assert!(output.contains("Unknown parameter part1_rounds, this day has no parameters"));
+    }
+
+    #[test]
+    fn run_without_input() {
+        let output = run(lines!("day 1" "run"));
+        assert!(output.contains("Select a day and provide an input first"));
+    }
+
+    #[test]
+    fn invalid_day() {
+        let output = run(lines!("day 0" "day 99"));
+        assert_eq!(output.matches("Expected a day between 1 and").count(), 2);
+    }
+}
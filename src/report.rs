@@ -0,0 +1,532 @@
+use crate::answer::Answer;
+use crate::answers::Verdict;
+use crate::parse::InputError;
+use crate::timing::Timing;
+use std::fmt::{self, Display, Formatter, Write as _};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unrecognized format {s}, expected text or json")),
+        }
+    }
+}
+
+pub struct DayReport {
+    pub day: usize,
+    /// Result of running the day, or why its input couldn't be read.
+    pub outcome: Result<DayRun, String>,
+}
+
+pub struct DayRun {
+    /// Time spent parsing the input, `None` when the input was streamed and
+    /// parsed along with each part or when the run panicked, or the parse
+    /// error.
+    pub parse: Result<Option<Timing>, InputError>,
+    pub parts: Vec<PartReport>,
+}
+
+pub struct PartReport {
+    pub part: u8,
+    pub outcome: Result<(Answer, Timing), String>,
+    /// Comparison against a stored answer, present when verifying.
+    pub verdict: Option<Verdict>,
+}
+
+/// Kinds of failures, ordered by increasing severity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FailureKind {
+    Mismatch,
+    Input,
+    Parse,
+    Solver,
+}
+
+impl FailureKind {
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Self::Input => 3,
+            Self::Parse | Self::Solver => 4,
+            Self::Mismatch => 5,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Failure {
+    pub kind: FailureKind,
+    pub day: usize,
+    pub part: Option<u8>,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "day {}", self.day)?;
+        if let Some(part) = self.part {
+            write!(f, " part {part}")?;
+        }
+        f.write_str(match self.kind {
+            FailureKind::Mismatch => " (wrong answer)",
+            FailureKind::Input => " (missing input)",
+            FailureKind::Parse => " (parse error)",
+            FailureKind::Solver => " (solver error)",
+        })
+    }
+}
+
+impl DayReport {
+    pub fn failures(&self) -> Vec<Failure> {
+        let kind = match &self.outcome {
+            Ok(DayRun { parse: Ok(_), .. }) => None,
+            Ok(DayRun { parse: Err(_), .. }) => Some(FailureKind::Parse),
+            Err(_) => Some(FailureKind::Input),
+        };
+        if let Some(kind) = kind {
+            return vec![Failure {
+                kind,
+                day: self.day,
+                part: None,
+            }];
+        }
+        self.parts()
+            .iter()
+            .filter_map(|part| {
+                let kind = match (&part.outcome, &part.verdict) {
+                    (Err(_), _) => FailureKind::Solver,
+                    (Ok(_), Some(Verdict::Fail { .. })) => FailureKind::Mismatch,
+                    _ => return None,
+                };
+                Some(Failure {
+                    kind,
+                    day: self.day,
+                    part: Some(part.part),
+                })
+            })
+            .collect()
+    }
+
+    /// Parts that were ran, empty if the input couldn't be read or parsed.
+    pub fn parts(&self) -> &[PartReport] {
+        self.outcome.as_ref().map_or(&[], |run| &run.parts)
+    }
+
+    /// Sum of median times of parsing and all parts that succeeded.
+    pub fn elapsed(&self) -> Duration {
+        let parse = self
+            .outcome
+            .iter()
+            .filter_map(|run| run.parse.as_ref().ok().and_then(Option::as_ref))
+            .map(Timing::median);
+        let parts = self
+            .parts()
+            .iter()
+            .filter_map(|part| part.outcome.as_ref().ok())
+            .map(|(_, timing)| timing.median());
+        parse.chain(parts).sum()
+    }
+
+    /// Writes the report to `out`, except for errors in text format which go
+    /// to `err`.
+    pub fn write(
+        &self,
+        format: Format,
+        with_header: bool,
+        out: &mut impl Write,
+        err: &mut impl Write,
+    ) -> io::Result<()> {
+        match format {
+            Format::Text => self.write_text(with_header, out, err),
+            Format::Json => writeln!(out, "{}", self.to_json()),
+        }
+    }
+
+    fn write_text(
+        &self,
+        with_header: bool,
+        stdout: &mut impl Write,
+        stderr: &mut impl Write,
+    ) -> io::Result<()> {
+        if with_header {
+            writeln!(stdout, "Day {}:", self.day)?;
+        }
+        let parts = match &self.outcome {
+            Ok(DayRun {
+                parse: Ok(timing),
+                parts,
+            }) => {
+                if let Some(timing) = timing {
+                    writeln!(stdout, "Parse: {timing}")?;
+                }
+                parts
+            }
+            Ok(DayRun { parse: Err(e), .. }) => {
+                return writeln!(stderr, "Day {} parsing failed: {}", self.day, e.render());
+            }
+            Err(e) => {
+                return writeln!(stderr, "Day {} failed: {e}", self.day);
+            }
+        };
+        for PartReport {
+            part,
+            outcome,
+            verdict,
+        } in parts
+        {
+            let (answer, timing) = match outcome {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    writeln!(stderr, "Day {} part {part} failed: {e}", self.day)?;
+                    continue;
+                }
+            };
+            // Multi-line answers that don't match are followed by the
+            // expected one in a block of its own, so the two can be told apart.
+            let expected_block = match verdict {
+                Some(Verdict::Fail { expected })
+                    if answer.is_multiline() || expected.is_multiline() =>
+                {
+                    Some(expected)
+                }
+                _ => None,
+            };
+            let verdict = match verdict {
+                None => String::new(),
+                Some(Verdict::Pass) => " PASS".into(),
+                Some(Verdict::Fail { .. }) if expected_block.is_some() => String::new(),
+                Some(Verdict::Fail { expected }) => format!(" FAIL, expected {expected}"),
+                Some(Verdict::Missing) => " MISSING".into(),
+            };
+            if answer.is_multiline() {
+                writeln!(stdout, "Part {part} ({timing}){verdict}:\n{answer}")?;
+            } else {
+                writeln!(stdout, "Part {part}: {answer} ({timing}){verdict}")?;
+            }
+            if let Some(expected) = expected_block {
+                writeln!(stdout, "FAIL, expected:\n{expected}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn to_json(&self) -> String {
+        let mut json = format!("{{\"day\":{}", self.day);
+        match &self.outcome {
+            Ok(DayRun { parse, parts }) => {
+                json.push_str(",\"error\":null,\"parse\":{");
+                match parse {
+                    Ok(Some(timing)) => {
+                        json.push_str("\"error\":null");
+                        write_json_timing(&mut json, timing);
+                    }
+                    Ok(None) => json.push_str("\"error\":null,\"elapsed_ns\":null"),
+                    Err(e) => {
+                        json.push_str("\"error\":");
+                        write_json_string(&mut json, &e.message);
+                        write!(
+                            json,
+                            ",\"line\":{},\"column\":{},\"elapsed_ns\":null",
+                            e.line, e.column
+                        )
+                        .unwrap();
+                    }
+                }
+                json.push_str("},\"parts\":[");
+                for (i, part) in parts.iter().enumerate() {
+                    if i != 0 {
+                        json.push(',');
+                    }
+                    part.write_json(&mut json);
+                }
+                json.push(']');
+            }
+            Err(e) => {
+                json.push_str(",\"error\":");
+                write_json_string(&mut json, e);
+                json.push_str(",\"parse\":null,\"parts\":[]");
+            }
+        }
+        json.push('}');
+        json
+    }
+}
+
+impl PartReport {
+    fn write_json(&self, json: &mut String) {
+        write!(json, "{{\"part\":{}", self.part).unwrap();
+        match &self.outcome {
+            Ok((answer, timing)) => {
+                json.push_str(",\"answer\":");
+                write_json_answer(json, answer);
+                json.push_str(",\"error\":null");
+                write_json_timing(json, timing);
+            }
+            Err(e) => {
+                json.push_str(",\"answer\":null,\"error\":");
+                write_json_string(json, e);
+                json.push_str(",\"elapsed_ns\":null");
+            }
+        }
+        match &self.verdict {
+            None => {}
+            Some(Verdict::Pass) => json.push_str(",\"verdict\":\"pass\""),
+            Some(Verdict::Fail { expected }) => {
+                json.push_str(",\"verdict\":\"fail\",\"expected\":");
+                write_json_answer(json, expected);
+            }
+            Some(Verdict::Missing) => json.push_str(",\"verdict\":\"missing\""),
+        }
+        json.push('}');
+    }
+}
+
+fn write_json_timing(json: &mut String, timing: &Timing) {
+    write!(
+        json,
+        ",\"elapsed_ns\":{},\"runs\":{}",
+        timing.median().as_nanos(),
+        timing.runs(),
+    )
+    .unwrap();
+    if timing.runs() > 1 {
+        write!(
+            json,
+            ",\"min_ns\":{},\"max_ns\":{}",
+            timing.min().as_nanos(),
+            timing.max().as_nanos(),
+        )
+        .unwrap();
+    }
+}
+
+/// Writes integers as numbers, text as a string and images as an array of
+/// rows drawn with `#` and `.`.
+fn write_json_answer(json: &mut String, answer: &Answer) {
+    match answer {
+        Answer::Integer(integer) => write!(json, "{integer}").unwrap(),
+        Answer::Text(text) => write_json_string(json, text),
+        Answer::Image(_) => {
+            json.push('[');
+            for (i, row) in answer.to_string().lines().enumerate() {
+                if i != 0 {
+                    json.push(',');
+                }
+                write_json_string(json, row);
+            }
+            json.push(']');
+        }
+    }
+}
+
+fn write_json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write_json_string, DayReport, DayRun, Failure, FailureKind, Format, PartReport};
+    use crate::answer::Answer;
+    use crate::answers::Verdict;
+    use crate::parse::InputError;
+    use crate::timing::Timing;
+
+    fn timing() -> Timing {
+        Timing::measure(1, || Ok::<_, ()>(())).unwrap().1
+    }
+
+    #[test]
+    fn json_string_escapes() {
+        let mut json = String::new();
+        write_json_string(&mut json, "#.\n\"a\\b\"\u{1}");
+        assert_eq!(json, r##""#.\n\"a\\b\"\u0001""##);
+    }
+
+    #[test]
+    fn json_report() {
+        let report = DayReport {
+            day: 3,
+            outcome: Ok(DayRun {
+                parse: Ok(Some(timing())),
+                parts: vec![
+                    PartReport {
+                        part: 1,
+                        outcome: Ok((42.into(), timing())),
+                        verdict: None,
+                    },
+                    PartReport {
+                        part: 2,
+                        outcome: Err("Unexpected \"x\"".into()),
+                        verdict: None,
+                    },
+                ],
+            }),
+        };
+        let json = report.to_json();
+        assert!(json.starts_with(r#"{"day":3,"error":null,"parse":{"error":null,"elapsed_ns":"#));
+        assert!(
+            json.contains(r#""runs":1},"parts":[{"part":1,"answer":42,"error":null,"elapsed_ns":"#)
+        );
+        assert!(json.ends_with(
+            r#""runs":1},{"part":2,"answer":null,"error":"Unexpected \"x\"","elapsed_ns":null}]}"#
+        ));
+    }
+
+    #[test]
+    fn json_verdict() {
+        let part = PartReport {
+            part: 2,
+            outcome: Ok((1.into(), timing())),
+            verdict: Some(Verdict::Fail { expected: 2.into() }),
+        };
+        let mut json = String::new();
+        part.write_json(&mut json);
+        assert!(json.ends_with(r#","verdict":"fail","expected":2}"#));
+    }
+
+    #[test]
+    fn text_image_mismatch() {
+        let timing = timing();
+        let elapsed = timing.to_string();
+        let report = DayReport {
+            day: 10,
+            outcome: Ok(DayRun {
+                parse: Ok(None),
+                parts: vec![PartReport {
+                    part: 2,
+                    outcome: Ok((Answer::parse("#.\n.#"), timing)),
+                    verdict: Some(Verdict::Fail {
+                        expected: Answer::parse("##\n.."),
+                    }),
+                }],
+            }),
+        };
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        report
+            .write(Format::Text, false, &mut out, &mut err)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("Part 2 ({elapsed}):\n#.\n.#\nFAIL, expected:\n##\n..\n")
+        );
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    fn json_image() {
+        let part = PartReport {
+            part: 2,
+            outcome: Ok((
+                Answer::Image(vec![vec![true, false], vec![false, true]]),
+                timing(),
+            )),
+            verdict: None,
+        };
+        let mut json = String::new();
+        part.write_json(&mut json);
+        assert!(json.starts_with(r##"{"part":2,"answer":["#.",".#"],"error":null"##));
+    }
+
+    #[test]
+    fn failures() {
+        let report = DayReport {
+            day: 5,
+            outcome: Ok(DayRun {
+                parse: Ok(Some(timing())),
+                parts: vec![
+                    PartReport {
+                        part: 1,
+                        outcome: Err("Invalid chunk".into()),
+                        verdict: None,
+                    },
+                    PartReport {
+                        part: 2,
+                        outcome: Ok((1.into(), timing())),
+                        verdict: Some(Verdict::Fail { expected: 2.into() }),
+                    },
+                ],
+            }),
+        };
+        let failures = report.failures();
+        assert_eq!(
+            failures,
+            [
+                Failure {
+                    kind: FailureKind::Solver,
+                    day: 5,
+                    part: Some(1),
+                },
+                Failure {
+                    kind: FailureKind::Mismatch,
+                    day: 5,
+                    part: Some(2),
+                },
+            ]
+        );
+        assert_eq!(failures[0].to_string(), "day 5 part 1 (solver error)");
+    }
+
+    #[test]
+    fn parse_failure() {
+        let report = DayReport {
+            day: 4,
+            outcome: Ok(DayRun {
+                parse: Err(InputError {
+                    message: "Missing first dash".into(),
+                    line: 3,
+                    column: 1,
+                    width: 3,
+                    text: "A Y".into(),
+                }),
+                parts: Vec::new(),
+            }),
+        };
+        assert_eq!(
+            report.failures(),
+            [Failure {
+                kind: FailureKind::Parse,
+                day: 4,
+                part: None,
+            }]
+        );
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":4,"error":null,"parse":{"error":"Missing first dash","line":3,"column":1,"elapsed_ns":null},"parts":[]}"#
+        );
+    }
+
+    #[test]
+    fn json_day_error() {
+        let report = DayReport {
+            day: 7,
+            outcome: Err("Unable to read input".into()),
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":7,"error":"Unable to read input","parse":null,"parts":[]}"#
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! Shortest path searches over graphs given by a neighbor function.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Shortest path to a goal, starting with one of the start nodes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N> {
+    /// Sum of the costs of the edges taken.
+    pub cost: u64,
+    /// Nodes from the start to the goal, both included.
+    pub nodes: Vec<N>,
+}
+
+/// Nodes reached so far, each with the one it was reached from.
+struct Visited<N> {
+    nodes: Vec<(N, Option<usize>)>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Visited<N> {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Index of the node, and whether it was seen for the first time.
+    fn insert(&mut self, node: N, parent: Option<usize>) -> (usize, bool) {
+        if let Some(&index) = self.indices.get(&node) {
+            return (index, false);
+        }
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push((node, parent));
+        (index, true)
+    }
+
+    fn path(&self, mut index: usize, cost: u64) -> Path<N> {
+        let mut nodes = Vec::new();
+        loop {
+            let (node, parent) = &self.nodes[index];
+            nodes.push(node.clone());
+            match parent {
+                Some(parent) => index = *parent,
+                None => break,
+            }
+        }
+        nodes.reverse();
+        Path { cost, nodes }
+    }
+}
+
+/// Breadth-first search, where every edge costs 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let (index, true) = visited.insert(start, None) {
+            queue.push_back((index, 0));
+        }
+    }
+    while let Some((index, cost)) = queue.pop_front() {
+        let node = visited.nodes[index].0.clone();
+        if is_goal(&node) {
+            return Some(visited.path(index, cost));
+        }
+        for neighbor in neighbors(&node) {
+            if let (neighbor, true) = visited.insert(neighbor, Some(index)) {
+                queue.push_back((neighbor, cost + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm, for edges with varying costs.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(starts, neighbors, is_goal, |_| 0)
+}
+
+/// A* search, guided by `heuristic`. For the path found to be the shortest,
+/// it must never overestimate the cost left to reach a goal, nor decrease by
+/// more than the cost of an edge along it.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut heuristic: impl FnMut(&N) -> u64,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut visited = Visited::new();
+    let mut costs = Vec::new();
+    let mut done = Vec::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let (index, true) = visited.insert(start, None) {
+            costs.push(0);
+            done.push(false);
+            queue.push(Reverse((heuristic(&visited.nodes[index].0), index)));
+        }
+    }
+    while let Some(Reverse((_, index))) = queue.pop() {
+        if done[index] {
+            continue;
+        }
+        done[index] = true;
+        let node = visited.nodes[index].0.clone();
+        let cost = costs[index];
+        if is_goal(&node) {
+            return Some(visited.path(index, cost));
+        }
+        for (neighbor, edge_cost) in neighbors(&node) {
+            let new_cost = cost + edge_cost;
+            let (neighbor_index, new) = visited.insert(neighbor, Some(index));
+            if new {
+                costs.push(new_cost);
+                done.push(false);
+            } else if done[neighbor_index] || new_cost >= costs[neighbor_index] {
+                continue;
+            } else {
+                costs[neighbor_index] = new_cost;
+                visited.nodes[neighbor_index].1 = Some(index);
+            }
+            let estimate = new_cost + heuristic(&visited.nodes[neighbor_index].0);
+            queue.push(Reverse((estimate, neighbor_index)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{astar, bfs, dijkstra};
+
+    /// Edges of a small weighted graph, where going through 2 is cheaper than
+    /// going straight from 1 to 3.
+    fn edges(node: &u32) -> Vec<(u32, u64)> {
+        match node {
+            0 => vec![(1, 1)],
+            1 => vec![(2, 1), (3, 5)],
+            2 => vec![(3, 1)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn breadth_first() {
+        let unweighted = |node: &u32| edges(node).into_iter().map(|(node, _)| node);
+        let path = bfs([0], unweighted, |&node| node == 4).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes, [0, 1, 3, 4]);
+        let path = bfs([0, 2], unweighted, |&node| node == 3).unwrap();
+        assert_eq!(path.nodes, [2, 3]);
+        assert_eq!(bfs([4], unweighted, |&node| node == 0), None);
+        assert_eq!(bfs([4], unweighted, |&node| node == 4).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn weighted() {
+        let path = dijkstra([0], edges, |&node| node == 4).unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.nodes, [0, 1, 2, 3, 4]);
+        assert_eq!(dijkstra([3], edges, |&node| node == 0), None);
+    }
+
+    #[test]
+    fn guided() {
+        // Open 10x10 grid with a wall at x = 5 except for its bottom row.
+        let neighbors = |&(x, y): &(i32, i32)| {
+            [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..10).contains(&y))
+                .filter(|&(x, y)| x != 5 || y == 9)
+                .map(|node| (node, 1))
+        };
+        let goal: (i32, i32) = (9, 0);
+        let distance = |&(x, y): &(i32, i32)| u64::from(goal.0.abs_diff(x) + goal.1.abs_diff(y));
+        let path = astar([(0, 0)], neighbors, |&node| node == goal, distance).unwrap();
+        assert_eq!(path.cost, 27);
+        assert_eq!(path.nodes.len(), 28);
+        assert!(path.nodes.contains(&(5, 9)));
+        assert_eq!(
+            dijkstra([(0, 0)], neighbors, |&node| node == goal)
+                .unwrap()
+                .cost,
+            27
+        );
+    }
+}
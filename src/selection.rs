@@ -0,0 +1,72 @@
+/// Parses a day selection such as `5`, `1-5`, `3,7,12`, `latest` or `all`
+/// into a sorted list of day numbers between 1 and `count`.
+pub fn parse_days(selection: &str, count: usize) -> Result<Vec<usize>, String> {
+    let parse_day = |day: &str| -> Result<usize, String> {
+        let day = match day.trim() {
+            "latest" => count,
+            day => day
+                .parse()
+                .map_err(|_| format!("Expected a day number, got {day:?}"))?,
+        };
+        if (1..=count).contains(&day) {
+            Ok(day)
+        } else {
+            Err(format!("Day {day} out of range, expected 1-{count}"))
+        }
+    };
+    if selection == "all" {
+        return Ok((1..=count).collect());
+    }
+    let mut days = Vec::new();
+    for item in selection.split(',') {
+        if let Some((start, end)) = item.split_once('-') {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+            if start > end {
+                return Err(format!("Range {item} is empty"));
+            }
+            days.extend(start..=end);
+        } else {
+            days.push(parse_day(item)?);
+        }
+    }
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod test {
+    use super::parse_days;
+
+    #[test]
+    fn single() {
+        assert_eq!(parse_days("12", 15), Ok(vec![12]));
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(parse_days("all", 3), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn latest() {
+        assert_eq!(parse_days("latest", 15), Ok(vec![15]));
+        assert_eq!(parse_days("13-latest", 15), Ok(vec![13, 14, 15]));
+    }
+
+    #[test]
+    fn ranges_and_lists() {
+        assert_eq!(parse_days("1-5", 15), Ok(vec![1, 2, 3, 4, 5]));
+        assert_eq!(parse_days("12,8,14", 15), Ok(vec![8, 12, 14]));
+        assert_eq!(parse_days("1-3,2,9", 15), Ok(vec![1, 2, 3, 9]));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_days("0", 15).is_err());
+        assert!(parse_days("16", 15).is_err());
+        assert!(parse_days("5-3", 15).is_err());
+        assert!(parse_days("a", 15).is_err());
+        assert!(parse_days("1,", 15).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(test)]
 #[macro_export]
 macro_rules! test {
     (__internal $ident:tt . $part:tt) => {};
@@ -8,7 +9,7 @@ macro_rules! test {
         $($tests:tt)*
     ) => {
         #[test] $( #[ $meta ] )* fn input() {
-            assert_eq!(($ident.$part)(include_str!("input")).unwrap(), concat!($output));
+            assert_eq!($ident.$part(&$ident.parse(&$crate::input::normalize(include_str!("input"))).unwrap(), &$crate::params::Params::default()).unwrap(), $crate::answer::Answer::parse(concat!($output)));
         }
         super::test!(__internal $ident.$part $($tests)*);
     };
@@ -17,11 +18,15 @@ macro_rules! test {
         __internal
         $ident:tt . $part:tt
         $( #[ $meta:meta ] )*
-        $name:ident: $input:expr => $output:expr,
+        $name:ident: $input:expr $(, $param:ident = $value:expr)* => $output:expr,
         $($tests:tt)*
     ) => {
         #[test] $( #[ $meta ] )* fn $name() {
-            assert_eq!(($ident.$part)($input).unwrap(), concat!($output));
+            let params = $crate::params::Params::new(
+                $ident.params(),
+                &[$((stringify!($param).to_string(), $value.to_string())),*],
+            ).unwrap();
+            assert_eq!($ident.$part(&$ident.parse(&$crate::input::normalize($input)).unwrap(), &params).unwrap(), $crate::answer::Answer::parse(concat!($output)));
         }
         super::test!(__internal $ident.$part $($tests)*);
     };
@@ -40,7 +45,8 @@ macro_rules! test {
     ) => {
         mod $part {
             #[allow(unused_imports)]
-            use crate::lines;
+            use $crate::lines;
+            use $crate::day::Day;
             use super::super::$ident;
             use super::*;
             super::test!(__internal $ident.$part $($tests)*);
@@ -48,18 +54,20 @@ macro_rules! test {
     };
 }
 
+/// Joins string literals into lines, each followed by a newline. A literal
+/// may be preceded by `-` to negate a number.
 #[macro_export]
 macro_rules! lines {
     (__internal $out:tt) => {
         concat!$out
     };
     (__internal ($($out:tt)*) - $input:tt $($rest:tt)*) => {
-        lines!(__internal ($($out)* concat!('-', $input), '\n',) $($rest)*)
+        $crate::lines!(__internal ($($out)* concat!('-', $input), '\n',) $($rest)*)
     };
     (__internal ($($out:tt)*) $input:tt $($rest:tt)*) => {
-        lines!(__internal ($($out)* $input, '\n',) $($rest)*)
+        $crate::lines!(__internal ($($out)* $input, '\n',) $($rest)*)
     };
     ($($t:tt)*) => {
-        lines!(__internal () $($t)*)
+        $crate::lines!(__internal () $($t)*)
     };
 }
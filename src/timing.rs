@@ -0,0 +1,85 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, Instant};
+
+pub struct Timing {
+    durations: Vec<Duration>,
+}
+
+impl Timing {
+    pub fn measure<T, E>(runs: usize, mut f: impl FnMut() -> Result<T, E>) -> Result<(T, Self), E> {
+        let mut durations = Vec::with_capacity(runs);
+        let mut output = None;
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            output = Some(f()?);
+            durations.push(start.elapsed());
+        }
+        durations.sort_unstable();
+        Ok((output.expect("at least one run"), Self { durations }))
+    }
+
+    pub fn runs(&self) -> usize {
+        self.durations.len()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.durations[0]
+    }
+
+    pub fn median(&self) -> Duration {
+        self.durations[self.durations.len() / 2]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.durations[self.durations.len() - 1]
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.durations.len() == 1 {
+            write!(f, "{:?}", self.median())
+        } else {
+            write!(
+                f,
+                "min {:?}, median {:?}, max {:?} over {} runs",
+                self.min(),
+                self.median(),
+                self.max(),
+                self.durations.len(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Timing;
+    use std::time::Duration;
+
+    #[test]
+    fn statistics() {
+        let timing = Timing {
+            durations: [1, 2, 5, 9, 30].map(Duration::from_millis).to_vec(),
+        };
+        assert_eq!(timing.min(), Duration::from_millis(1));
+        assert_eq!(timing.median(), Duration::from_millis(5));
+        assert_eq!(timing.max(), Duration::from_millis(30));
+        assert_eq!(
+            timing.to_string(),
+            "min 1ms, median 5ms, max 30ms over 5 runs"
+        );
+    }
+
+    #[test]
+    fn measure_runs_requested_times() {
+        let mut runs = 0;
+        let (output, timing) = Timing::measure(3, || {
+            runs += 1;
+            Ok::<_, ()>(runs)
+        })
+        .unwrap();
+        assert_eq!(output, 3);
+        assert_eq!(timing.durations.len(), 3);
+    }
+}
@@ -0,0 +1,108 @@
+use advent_of_code_2022::answer::Answer;
+use advent_of_code_2022::report::{DayReport, Format};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Reruns `run` every time the modification time of `path` changes, printing
+/// how the answers differ from the previous run. Only returns on failure.
+pub fn watch(
+    path: &Path,
+    format: Format,
+    mut run: impl FnMut(&str) -> DayReport,
+) -> io::Result<()> {
+    let mut last_modified = None;
+    let mut previous_answers = HashMap::new();
+    loop {
+        let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+        match modified {
+            Ok(modified) if last_modified != Some(modified) => {
+                last_modified = Some(modified);
+                match fs::read_to_string(path) {
+                    Ok(input) => {
+                        let report = run(&input);
+                        report.write(format, false, &mut io::stdout(), &mut io::stderr())?;
+                        if format == Format::Text {
+                            write_changes(&report, &mut previous_answers)?;
+                        }
+                    }
+                    Err(e) => eprintln!("Unable to read {}: {e}", path.display()),
+                }
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Unable to read metadata of {}: {e}", path.display()),
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn write_changes(report: &DayReport, previous_answers: &mut HashMap<u8, Answer>) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    for part in report.parts() {
+        let Ok((answer, _)) = &part.outcome else {
+            continue;
+        };
+        if let Some(previous) = previous_answers.insert(part.part, answer.clone()) {
+            if previous == *answer {
+                writeln!(stdout, "Part {} unchanged", part.part)?;
+            } else {
+                writeln!(stdout, "Part {} changed:", part.part)?;
+                write!(
+                    stdout,
+                    "{}",
+                    diff(&previous.to_string(), &answer.to_string())
+                )?;
+            }
+        }
+    }
+    writeln!(stdout, "Watching for changes...")
+}
+
+/// Line by line comparison of two answers, marking removed lines with `-`
+/// and added lines with `+`.
+fn diff(old: &str, new: &str) -> String {
+    let mut old_lines = old.lines();
+    let mut new_lines = new.lines();
+    let mut output = String::new();
+    loop {
+        match (old_lines.next(), new_lines.next()) {
+            (None, None) => return output,
+            (Some(old), Some(new)) if old == new => {
+                writeln!(output, "  {old}").unwrap();
+            }
+            (old, new) => {
+                if let Some(old) = old {
+                    writeln!(output, "- {old}").unwrap();
+                }
+                if let Some(new) = new {
+                    writeln!(output, "+ {new}").unwrap();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::diff;
+    use advent_of_code_2022::lines;
+
+    #[test]
+    fn single_line() {
+        assert_eq!(diff("24000", "45000"), lines!("- 24000" "+ 45000"));
+    }
+
+    #[test]
+    fn multi_line() {
+        assert_eq!(
+            diff("#.\n.#\n", "#.\n##\n#."),
+            lines!("  #." "- .#" "+ ##" "+ #.")
+        );
+    }
+}